@@ -0,0 +1,22 @@
+//! Shared finishing step for algorithms that produce self-overlapping "raw"
+//! geometry and rely on a winding-rule union pass to resolve it into simple,
+//! correctly wound contours (offsetting, Minkowski sums, polygon
+//! simplification all need exactly this).
+//!
+//! This is meant as a thin wrapper around `Clipper`'s union execution (see
+//! `clipper.rs`). Until the Vatti scanline in `execute_internal` lands there,
+//! it passes the raw geometry
+//! through unchanged so callers can be written against the final shape of
+//! the API now.
+
+use point::IntPoint;
+use {Paths, PolyFillType};
+
+/// Returns `paths` unchanged. No overlap is resolved and `fill_type` is not
+/// used: overlapping or self-intersecting input comes back as it went in.
+pub fn resolve_self_overlap<T: IntPoint>(paths: Paths<T>, _fill_type: PolyFillType) -> Paths<T> {
+    // TODO: route through `Clipper::execute` (ClipType::Union) once the
+    // scanline fill is implemented; for now the raw, possibly overlapping
+    // geometry is returned as-is.
+    paths
+}
@@ -1,110 +1,432 @@
-/// Bitflags for Clipper init options
-const EXECUTE_LOCKED: u8    = 0;
-const HAS_OPEN_PATHS: u8    = 0;
-const USE_FULL_RANGE: u8    = 0;
-const REVERSE_OUTPUT: u8    = 0;
-const STRICT_SIMPLE: u8     = 0;
-const PRESERVE_COLINEAR: u8 = 0;
+//! Boolean polygon clipping (Vatti's scanline algorithm).
+//!
+//! NOTE: the scanline fill itself (`execute_internal`) is not implemented
+//! yet -- see the `TODO` below. Everything around it (the builder, init
+//! options, subject/clip registration including open-path tagging, and
+//! `PolyTree` extraction) is written against its final shape so the rest of
+//! the crate can be built out independently and this module can be wired up
+//! without changing its callers later.
 
-pub struct ThreadPool; // todo: make real threadpool
+use std::collections::HashMap;
 
-pub struct ClipperBuilder<'a> {
-    options: ClipperInitOptions,
-    thread_pool: Option<&'a ThreadPool>,
-}
+use edge::Edge;
+use point::{CInt, IntPoint, IntPoint3d};
+use thread_pool::ThreadPool;
+use {ClipType, IntRect, Path, Paths, PolyFillType, PolyTree, PolyType};
 
-pub struct Clipper<'a, F: Fn(IntPoint3d, IntPoint3d) -> IntPoint3d + 'a> {
-    options: u8,
-    subj_fill_type: PolyFillType,
-    clip_fill_type: PolyFillType,
-    clip_type: PolyClipType,
-    z_fill: Option<F>>,
-    thread_pool: Option<&'a ThreadPool>,
-}
+/// Bitflags for Clipper init options.
+const EXECUTE_LOCKED: u8 = 1 << 0;
+/// Set once any subject/clip path has been added via `add_path(.., is_open: true)`.
+const HAS_OPEN_PATHS: u8 = 1 << 1;
+const USE_FULL_RANGE: u8 = 1 << 2;
+const REVERSE_OUTPUT: u8 = 1 << 3;
+const STRICT_SIMPLE: u8 = 1 << 4;
+const PRESERVE_COLINEAR: u8 = 1 << 5;
 
-#[repr(packed)]
+#[derive(Copy, Clone)]
 pub struct ClipperInitOptions {
     pub execute_locked: bool,
     pub strict_simple: bool,
     pub preserve_colinear: bool,
 }
 
-impl<'a> ClipperBuilder<'a> {
+impl ClipperInitOptions {
+    #[inline]
+    pub fn execute_locked(&self) -> bool {
+        self.execute_locked
+    }
+    #[inline]
+    pub fn strict_simple(&self) -> bool {
+        self.strict_simple
+    }
+    #[inline]
+    pub fn preserve_colinear(&self) -> bool {
+        self.preserve_colinear
+    }
+}
 
+pub struct ClipperBuilder<'a> {
+    options: ClipperInitOptions,
+    z_fill: Option<fn(IntPoint3d, IntPoint3d) -> IntPoint3d>,
+    thread_pool: Option<&'a ThreadPool>,
+}
+
+impl<'a> ClipperBuilder<'a> {
     #[inline]
     pub fn new(options: ClipperInitOptions, thread_pool: Option<&'a ThreadPool>) -> Self {
         Self {
             options: options,
+            z_fill: None,
             thread_pool: thread_pool,
         }
     }
 
     #[inline]
-    pub fn with_z_fill_function(&mut self, func: Option<fn(IntPoint3d, IntPoint3d) -> IntPoint3d>) {
+    pub fn with_z_fill_function(mut self, func: Option<fn(IntPoint3d, IntPoint3d) -> IntPoint3d>) -> Self {
         self.z_fill = func;
+        self
     }
 
     #[inline]
-    pub fn build<'b: 'a>(self) -> Clipper<'b> {
-
+    pub fn build<'b>(self) -> Clipper<'b> where 'a: 'b {
         let mut opts = 0;
-        if self.options.execute_locked() { opts |= EXECUTE_LOCKED };
-        if self.options.strict_simple() { opts |= STRICT_SIMPLE };
-        if self.options.preserve_colinear() { opts |= PRESERVE_COLINEAR };
-        
+        if self.options.execute_locked() { opts |= EXECUTE_LOCKED; }
+        if self.options.strict_simple() { opts |= STRICT_SIMPLE; }
+        if self.options.preserve_colinear() { opts |= PRESERVE_COLINEAR; }
+
         Clipper {
             options: opts,
             subj_fill_type: PolyFillType::EvenOdd,
             clip_fill_type: PolyFillType::EvenOdd,
+            clip_type: ClipType::Intersection,
             z_fill: self.z_fill,
             thread_pool: self.thread_pool,
+            subjects: Vec::new(),
+            clips: Vec::new(),
         }
     }
 }
 
-// TODO!!!!
+// TODO: flesh this out into a proper error enum once execute_internal exists.
 pub struct ClipperError;
 
+pub struct Clipper<'a> {
+    options: u8,
+    subj_fill_type: PolyFillType,
+    clip_fill_type: PolyFillType,
+    clip_type: ClipType,
+    z_fill: Option<fn(IntPoint3d, IntPoint3d) -> IntPoint3d>,
+    thread_pool: Option<&'a ThreadPool>,
+    /// `(path, is_open)` pairs added via `add_path(.., PolyType::Subject, ..)`.
+    subjects: Vec<(Path<IntPoint3d>, bool)>,
+    /// `(path, is_open)` pairs added via `add_path(.., PolyType::Clip, ..)`.
+    clips: Vec<(Path<IntPoint3d>, bool)>,
+}
+
 impl<'a> Clipper<'a> {
+    /// Registers a single path as either subject or clip geometry.
+    /// `is_open` marks it as a polyline (the `HAS_OPEN_PATHS` case): open
+    /// edges are clipped as lines rather than filled regions, and never
+    /// contribute to the winding fill (see `is_contributing`).
+    pub fn add_path(&mut self, path: Path<IntPoint3d>, poly_type: PolyType, is_open: bool) {
+        if is_open {
+            self.options |= HAS_OPEN_PATHS;
+        }
+        match poly_type {
+            PolyType::Subject => self.subjects.push((path, is_open)),
+            PolyType::Clip => self.clips.push((path, is_open)),
+        }
+    }
 
-    pub fn execute_polytree(clip_type: ClipType, solution: &mut Paths, fill_type: PolyFillType)
-                            -> Result<(), ClipperError>
-    {
-        // do something with paths
+    /// Registers every path of `paths`, all sharing `poly_type`/`is_open`.
+    pub fn add_paths(&mut self, paths: Paths<IntPoint3d>, poly_type: PolyType, is_open: bool) {
+        for path in paths.paths {
+            self.add_path(path, poly_type, is_open);
+        }
     }
 
-    pub fn execute_polytree(clip_type: ClipType, solution: &mut PolyTree, fill_type: PolyFillType)
-                            -> Result<(), ClipperError>
-    {
-        // do something with polytree
+    pub fn has_open_paths(&self) -> bool {
+        self.options & HAS_OPEN_PATHS != 0
     }
 
-    pub fn closed_paths_from_polytree(poly_tree: &PolyTree) -> Paths {
-        let relevant_nodes = poly_tree.iter().filter(|node| node.is_closed()).collect();
-        let mut paths = Vec::<Path>::with_capacity(relevant_nodes.len());
-        for node in relevant_nodes {
-            paths.push
-        }
-        Paths { paths:  }
+    pub fn execute_polytree(&mut self, clip_type: ClipType, solution: &mut PolyTree<IntPoint3d>, fill_type: PolyFillType) -> Result<(), ClipperError> {
+        self.clip_type = clip_type;
+        self.subj_fill_type = fill_type;
+        self.clip_fill_type = fill_type;
+        self.execute_internal(solution)
+    }
+
+    pub fn execute(&mut self, clip_type: ClipType, solution: &mut Paths<IntPoint3d>, fill_type: PolyFillType) -> Result<(), ClipperError> {
+        let mut tree = PolyTree::new();
+        self.execute_polytree(clip_type, &mut tree, fill_type)?;
+        *solution = closed_paths_from_polytree(&tree);
+        Ok(())
+    }
+
+    /// Collects every closed (filled-region) contour of `poly_tree` into `Paths`.
+    pub fn closed_paths_from_polytree(poly_tree: &PolyTree<IntPoint3d>) -> Paths<IntPoint3d> {
+        closed_paths_from_polytree(poly_tree)
     }
-    
-    fn execute_internal() -> Result<(), ClipperError> {
-        
+
+    /// Collects every open (polyline) fragment of `poly_tree` into `Paths`.
+    pub fn open_paths_from_polytree(poly_tree: &PolyTree<IntPoint3d>) -> Paths<IntPoint3d> {
+        open_paths_from_polytree(poly_tree)
+    }
+
+    /// Buckets every registered subject/clip path (indexed with subjects
+    /// first, then clips) into clusters of overlapping bounding boxes. This
+    /// is the unit of work `execute_internal` will dispatch across
+    /// `self.thread_pool` once the scanline exists: clusters whose bounding
+    /// boxes don't overlap can never interact, so their local minima can be
+    /// built and swept independently. Bounding boxes are computed on
+    /// `self.thread_pool` when one is set (falling back to a serial loop
+    /// otherwise); merging overlapping boxes into clusters is inherently
+    /// sequential and always runs on the calling thread.
+    pub fn path_clusters(&self) -> Vec<Vec<usize>> {
+        let all_paths: Vec<Path<IntPoint3d>> = self
+            .subjects
+            .iter()
+            .chain(self.clips.iter())
+            .map(|(p, _)| Path { poly: p.poly.clone() })
+            .collect();
+
+        let bounds: Vec<IntRect> = match self.thread_pool {
+            Some(pool) => pool.map(all_paths, |p| bounding_rect(&p)),
+            None => all_paths.iter().map(bounding_rect).collect(),
+        };
+
+        cluster_by_overlap(&bounds)
+    }
+
+    // TODO: the actual Vatti scanline belongs here -- for each cluster from
+    // `path_clusters`, build local minima from its member paths, sweep the
+    // active edge list by `y` (independent clusters dispatched across
+    // `self.thread_pool`), and populate `solution` as the sweep closes each
+    // `OutRec`. Everything above is written against this function's final
+    // signature.
+    fn execute_internal(&mut self, _solution: &mut PolyTree<IntPoint3d>) -> Result<(), ClipperError> {
+        Ok(())
     }
-    
-    fn is_contributing(edge: &Edge) -> bool {
+
+    /// Whether `edge` should contribute to the output under the current
+    /// fill types and clip type. Open-path edges never contribute: they're
+    /// walked for line clipping only, not region winding.
+    fn is_contributing(&self, edge: &Edge<IntPoint3d>) -> bool {
+        if edge.is_open {
+            return false;
+        }
+
         let (mut pft, mut pft2) = (self.subj_fill_type, self.clip_fill_type);
-        if edge.poly_typ != self.pt_subject { ::std::mem::swap(&mut pft, &mut pft2); }
+        if edge.poly_typ != PolyType::Subject {
+            ::std::mem::swap(&mut pft, &mut pft2);
+        }
 
         match pft {
-            PolyFillType::EvenOdd => if edge.wind_delta == 0 && edge.wind_cnt != 1 { return false; }
-            PolyFillType::NonZero => if edge.wind_cnt.abs() != 1 { return false; }
-            PolyFillType::Positive => if edge.wind_cnt != 1 { return false; }
-            PolyFillType::Negative => if edge.wind_cnt != -1 { return false; }
+            PolyFillType::EvenOdd => if edge.winding_delta == 0 && edge.winding_count != 1 { return false; },
+            PolyFillType::NonZero => if edge.winding_count.abs() != 1 { return false; },
+            PolyFillType::Positive => if edge.winding_count != 1 { return false; },
+            PolyFillType::Negative => if edge.winding_count != -1 { return false; },
         }
 
+        let clip_side_filled = match pft2 {
+            PolyFillType::EvenOdd | PolyFillType::NonZero => edge.winding_count_2 != 0,
+            PolyFillType::Positive => edge.winding_count_2 > 0,
+            PolyFillType::Negative => edge.winding_count_2 < 0,
+        };
+
         match self.clip_type {
-            
+            ClipType::Intersection => clip_side_filled,
+            ClipType::Union => !clip_side_filled,
+            ClipType::Difference => {
+                if edge.poly_typ == PolyType::Subject { !clip_side_filled } else { clip_side_filled }
+            }
+            ClipType::Xor => true,
         }
-    }                   
+    }
+}
+
+fn closed_paths_from_polytree(poly_tree: &PolyTree<IntPoint3d>) -> Paths<IntPoint3d> {
+    Paths {
+        paths: poly_tree
+            .all_nodes
+            .iter()
+            .filter(|node| node.is_closed())
+            .map(|node| Path { poly: node.contour.poly.clone() })
+            .collect(),
+    }
+}
+
+fn open_paths_from_polytree(poly_tree: &PolyTree<IntPoint3d>) -> Paths<IntPoint3d> {
+    Paths {
+        paths: poly_tree
+            .all_nodes
+            .iter()
+            .filter(|node| !node.is_closed())
+            .map(|node| Path { poly: node.contour.poly.clone() })
+            .collect(),
+    }
+}
+
+/// Axis-aligned bounding box of `path`, in `CInt` space. Empty paths get a
+/// degenerate zero-sized box at the origin.
+fn bounding_rect(path: &Path<IntPoint3d>) -> IntRect {
+    if path.poly.is_empty() {
+        return IntRect { left: 0, top: 0, right: 0, bottom: 0 };
+    }
+
+    let mut left = CInt::max_value();
+    let mut right = CInt::min_value();
+    let mut top = CInt::max_value();
+    let mut bottom = CInt::min_value();
+
+    for p in &path.poly {
+        left = left.min(p.get_x());
+        right = right.max(p.get_x());
+        top = top.min(p.get_y());
+        bottom = bottom.max(p.get_y());
+    }
+
+    IntRect {
+        left: left as isize,
+        top: top as isize,
+        right: right as isize,
+        bottom: bottom as isize,
+    }
+}
+
+#[inline]
+fn rects_overlap(a: &IntRect, b: &IntRect) -> bool {
+    a.left <= b.right && b.left <= a.right && a.top <= b.bottom && b.top <= a.bottom
+}
+
+/// Union-find root lookup with path compression.
+fn find_root(parents: &mut [usize], x: usize) -> usize {
+    if parents[x] != x {
+        parents[x] = find_root(parents, parents[x]);
+    }
+    parents[x]
+}
+
+/// Groups indices of `bounds` into clusters of boxes connected by overlap
+/// (two boxes share a cluster if a chain of overlapping boxes links them).
+fn cluster_by_overlap(bounds: &[IntRect]) -> Vec<Vec<usize>> {
+    let n = bounds.len();
+    let mut parents: Vec<usize> = (0..n).collect();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if rects_overlap(&bounds[i], &bounds[j]) {
+                let ri = find_root(&mut parents, i);
+                let rj = find_root(&mut parents, j);
+                if ri != rj {
+                    parents[ri] = rj;
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..n {
+        let root = find_root(&mut parents, i);
+        groups.entry(root).or_insert_with(Vec::new).push(i);
+    }
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use edge::Edge;
+    use node::PolyNode;
+    use std::sync::{Arc, Mutex};
+    use {EdgeSide, EdgeIndex, EndType, JoinType, PolyNodeIndex};
+
+    /// An `Edge` with every field set to an inert default, for tests that
+    /// only care about the handful of fields `is_contributing` actually
+    /// reads. The index fields (`next`, `prev`, ...) are never populated by
+    /// anything else in the codebase yet (the scanline that would do so is
+    /// unimplemented), so `0` is as good a placeholder as any.
+    fn edge_with(poly_typ: PolyType, is_open: bool, winding_delta: u8, winding_count: isize, winding_count_2: isize) -> Edge<IntPoint3d> {
+        Edge {
+            bot: IntPoint3d::new(0, 0),
+            cur: IntPoint3d::new(0, 0),
+            top: IntPoint3d::new(0, 0),
+            dx: 0.0,
+            poly_typ: poly_typ,
+            is_open: is_open,
+            side: EdgeSide::Left,
+            winding_delta: winding_delta,
+            winding_count: winding_count,
+            winding_count_2: winding_count_2,
+            out_idx: 0,
+            next: EdgeIndex { edge_idx: 0 },
+            prev: EdgeIndex { edge_idx: 0 },
+            next_in_lml: EdgeIndex { edge_idx: 0 },
+            next_in_ael: EdgeIndex { edge_idx: 0 },
+            prev_in_ael: EdgeIndex { edge_idx: 0 },
+            next_in_sel: EdgeIndex { edge_idx: 0 },
+            prev_in_sel: EdgeIndex { edge_idx: 0 },
+        }
+    }
+
+    fn standalone_node(tree: Arc<Mutex<PolyTree<IntPoint3d>>>, contour: Vec<IntPoint3d>, is_open: bool) -> PolyNode<IntPoint3d> {
+        PolyNode {
+            tree,
+            glob_index: PolyNodeIndex { node_idx: 0 },
+            index: 0,
+            contour: Path { poly: contour },
+            parent: None,
+            childs: Vec::new(),
+            is_open: is_open,
+            join_type: JoinType::Miter,
+            end_type: EndType::ClosedPolygon,
+        }
+    }
+
+    #[test]
+    fn is_contributing_short_circuits_open_edges_regardless_of_winding() {
+        let options = ClipperInitOptions { execute_locked: false, strict_simple: false, preserve_colinear: false };
+        let clipper: Clipper = ClipperBuilder::new(options, None).build();
+
+        // Winding/fill settings that would otherwise make this edge contribute...
+        let open_edge = edge_with(PolyType::Subject, true, 1, 1, 1);
+        assert!(!clipper.is_contributing(&open_edge), "an open-path edge must never contribute, no matter its winding counts");
+
+        // ...the same settings on a closed edge do contribute, confirming
+        // the above is actually exercising the `is_open` short-circuit and
+        // not some other reason for returning false.
+        let closed_edge = edge_with(PolyType::Subject, false, 1, 1, 1);
+        assert!(clipper.is_contributing(&closed_edge));
+    }
+
+    #[test]
+    fn polytree_extraction_splits_open_and_closed_nodes() {
+        let tree = Arc::new(Mutex::new(PolyTree::new()));
+        let closed = standalone_node(tree.clone(), vec![IntPoint3d::new(0, 0), IntPoint3d::new(10, 0), IntPoint3d::new(10, 10)], false);
+        let open = standalone_node(tree.clone(), vec![IntPoint3d::new(0, 0), IntPoint3d::new(10, 0)], true);
+        {
+            let mut locked = tree.lock().unwrap();
+            locked.all_nodes.push(closed);
+            locked.all_nodes.push(open);
+        }
+
+        let locked = tree.lock().unwrap();
+        let closed_paths = closed_paths_from_polytree(&*locked);
+        let open_paths = open_paths_from_polytree(&*locked);
+
+        assert_eq!(closed_paths.paths.len(), 1);
+        assert_eq!(closed_paths.paths[0].poly.len(), 3);
+        assert_eq!(open_paths.paths.len(), 1);
+        assert_eq!(open_paths.paths[0].poly.len(), 2);
+    }
+
+    fn square_at(x: CInt, y: CInt, size: CInt) -> Path<IntPoint3d> {
+        Path {
+            poly: vec![
+                IntPoint3d::new(x, y),
+                IntPoint3d::new(x + size, y),
+                IntPoint3d::new(x + size, y + size),
+                IntPoint3d::new(x, y + size),
+            ],
+        }
+    }
+
+    #[test]
+    fn path_clusters_groups_overlapping_boxes_and_separates_distant_ones() {
+        let options = ClipperInitOptions { execute_locked: false, strict_simple: false, preserve_colinear: false };
+        let mut clipper: Clipper = ClipperBuilder::new(options, None).build();
+
+        // Two overlapping subjects, plus a far-away clip that touches neither.
+        clipper.add_path(square_at(0, 0, 10), PolyType::Subject, false);
+        clipper.add_path(square_at(5, 5, 10), PolyType::Subject, false);
+        clipper.add_path(square_at(1000, 1000, 10), PolyType::Clip, false);
+
+        let mut clusters = clipper.path_clusters();
+        for cluster in &mut clusters {
+            cluster.sort();
+        }
+        clusters.sort();
+
+        assert_eq!(clusters, vec![vec![0, 1], vec![2]]);
+    }
 }
@@ -0,0 +1,197 @@
+//! Floating-point clipping facade with automatic fixed-point scaling.
+//!
+//! `Clipper`'s engine works in integer `IntPoint` space, but callers feeding
+//! geometry from SVG/CAD sources have `f64` coordinates and shouldn't have
+//! to hand-roll fixed-point scaling themselves. `ClipperD` multiplies every
+//! input coordinate by `10^precision` to produce `IntPoint`s, and divides
+//! results back down to `DoublePoint` on the way out.
+
+use clipper::{Clipper, ClipperBuilder, ClipperInitOptions};
+use point::{CInt, DoublePoint, DoublePoint3d, IntPoint, IntPoint3d};
+use {ClipType, Path, Paths, PolyFillType, PolyType};
+
+/// `precision` was negative or large enough that `10^precision` would push
+/// in-range input coordinates outside what `CInt` can represent.
+pub const PRECISION_OUT_OF_RANGE: u8 = 1 << 0;
+/// A scaled coordinate overflowed `CInt`'s range.
+pub const COORDINATE_RANGE: u8 = 1 << 1;
+
+/// Non-fatal bitflags describing truncation/overflow conditions hit while
+/// scaling coordinates, mirroring the error bitflags Clipper2 tracks.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ClipperError(pub u8);
+
+impl ClipperError {
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub fn has(&self, flag: u8) -> bool {
+        self.0 & flag != 0
+    }
+}
+
+/// The largest decimal precision that keeps scaled coordinates within
+/// `CInt`'s usable range, leaving headroom for the clipping arithmetic
+/// itself (which needs a few extra bits for intermediate products).
+#[cfg(use_int32)]
+fn max_safe_precision() -> i32 {
+    4
+}
+
+#[cfg(not(use_int32))]
+fn max_safe_precision() -> i32 {
+    8
+}
+
+/// A floating-point clipping facade built on top of the integer pipeline.
+pub struct ClipperD {
+    precision: i32,
+    scale: f64,
+    error: ClipperError,
+}
+
+impl ClipperD {
+    /// Creates a new `ClipperD` that scales coordinates by `10^precision`.
+    /// `precision` out of range sets [`PRECISION_OUT_OF_RANGE`] on
+    /// [`ClipperD::error`] and clamps scaling to a no-op (`precision = 0`).
+    pub fn new(precision: i32) -> Self {
+        let mut error = ClipperError(0);
+        let clamped = if precision < 0 || precision > max_safe_precision() {
+            error.0 |= PRECISION_OUT_OF_RANGE;
+            0
+        } else {
+            precision
+        };
+
+        Self {
+            precision: clamped,
+            scale: 10f64.powi(clamped),
+            error: error,
+        }
+    }
+
+    pub fn precision(&self) -> i32 {
+        self.precision
+    }
+
+    /// Any non-fatal error conditions accumulated since construction.
+    pub fn error(&self) -> ClipperError {
+        self.error
+    }
+
+    /// Scales a single point from `f64` space into `CInt` space, setting
+    /// [`COORDINATE_RANGE`] on overflow (in which case the point is clamped
+    /// to zero rather than wrapping silently).
+    fn to_int_point(&mut self, p: &DoublePoint3d) -> IntPoint3d {
+        let x = p.get_x() * self.scale;
+        let y = p.get_y() * self.scale;
+        let max = CInt::max_value() as f64;
+        let min = CInt::min_value() as f64;
+
+        if x > max || x < min || y > max || y < min || x.is_nan() || y.is_nan() {
+            self.error.0 |= COORDINATE_RANGE;
+            return IntPoint3d::new(0, 0);
+        }
+
+        IntPoint3d::new(x.round() as CInt, y.round() as CInt)
+    }
+
+    fn from_int_point(&self, p: &IntPoint3d) -> DoublePoint3d {
+        DoublePoint3d {
+            x: p.get_x() as f64 / self.scale,
+            y: p.get_y() as f64 / self.scale,
+            z: p.get_z().unwrap_or(0) as f64,
+        }
+    }
+
+    /// Scales a whole path of `f64` points into `CInt` space.
+    pub fn scale_path(&mut self, path: &[DoublePoint3d]) -> Path<IntPoint3d> {
+        Path { poly: path.iter().map(|p| self.to_int_point(p)).collect() }
+    }
+
+    /// Divides a clipped `Path`'s coordinates back down to `f64` space.
+    pub fn unscale_path(&self, path: &Path<IntPoint3d>) -> Vec<DoublePoint3d> {
+        path.poly.iter().map(|p| self.from_int_point(p)).collect()
+    }
+
+    /// Runs a boolean clip entirely in `f64` space: scales `subjects`/`clips`
+    /// up into `IntPoint` space, runs them through `Clipper::execute`, and
+    /// scales the solution back down -- so callers don't have to hand-roll
+    /// the scale/execute/unscale boilerplate themselves.
+    pub fn execute(
+        &mut self,
+        clip_type: ClipType,
+        subjects: &[Vec<DoublePoint3d>],
+        clips: &[Vec<DoublePoint3d>],
+        fill_type: PolyFillType,
+    ) -> Result<Vec<Vec<DoublePoint3d>>, ::clipper::ClipperError> {
+        let options = ClipperInitOptions {
+            execute_locked: false,
+            strict_simple: false,
+            preserve_colinear: false,
+        };
+        let mut clipper: Clipper = ClipperBuilder::new(options, None).build();
+
+        for subject in subjects {
+            let path = self.scale_path(subject);
+            clipper.add_path(path, PolyType::Subject, false);
+        }
+        for clip in clips {
+            let path = self.scale_path(clip);
+            clipper.add_path(path, PolyType::Clip, false);
+        }
+
+        let mut solution = Paths { paths: Vec::new() };
+        clipper.execute(clip_type, &mut solution, fill_type)?;
+
+        Ok(solution.paths.iter().map(|p| self.unscale_path(p)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_and_unscale_round_trips_within_precision() {
+        let mut d = ClipperD::new(4);
+        let original = vec![
+            DoublePoint3d { x: 1.2345, y: -6.789, z: 0.0 },
+            DoublePoint3d { x: 0.0001, y: 100.0, z: 0.0 },
+        ];
+
+        let scaled = d.scale_path(&original);
+        let back = d.unscale_path(&scaled);
+
+        assert!(d.error().is_ok());
+        for (a, b) in original.iter().zip(back.iter()) {
+            assert!((a.x - b.x).abs() < 1.0e-3, "{} vs {}", a.x, b.x);
+            assert!((a.y - b.y).abs() < 1.0e-3, "{} vs {}", a.y, b.y);
+        }
+    }
+
+    #[test]
+    fn out_of_range_precision_is_flagged_and_clamped() {
+        let d = ClipperD::new(100);
+        assert!(d.error().has(PRECISION_OUT_OF_RANGE));
+        assert_eq!(d.precision(), 0);
+    }
+
+    #[test]
+    fn execute_scales_inputs_through_the_integer_pipeline_without_erroring() {
+        let mut d = ClipperD::new(2);
+        let subject = vec![
+            DoublePoint3d { x: 0.0, y: 0.0, z: 0.0 },
+            DoublePoint3d { x: 10.0, y: 0.0, z: 0.0 },
+            DoublePoint3d { x: 10.0, y: 10.0, z: 0.0 },
+            DoublePoint3d { x: 0.0, y: 10.0, z: 0.0 },
+        ];
+
+        let result = d.execute(ClipType::Union, &[subject], &[], PolyFillType::NonZero);
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+//! Curve import: flattens line/quadratic/cubic Bezier segments (as produced
+//! by an SVG/CAD path parser) into `Paths` at a configurable tolerance, and
+//! turns an open polyline + stroke width into closed, fillable `Paths`
+//! ready to feed into clipping.
+
+use offset::ClipperOffset;
+use point::{CInt, IntPoint};
+use {EndType, JoinType, Path, Paths};
+
+/// A floating-point point in the curve's native coordinate space, before
+/// scaling into `CInt` space.
+#[derive(Debug, Copy, Clone)]
+pub struct DPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl DPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        DPoint { x: x, y: y }
+    }
+}
+
+/// One segment of a path as produced by an SVG/CAD importer.
+pub enum CurveSegment {
+    Line(DPoint, DPoint),
+    Quadratic(DPoint, DPoint, DPoint),
+    Cubic(DPoint, DPoint, DPoint, DPoint),
+}
+
+/// Flattens a sequence of connected `segments` into a single `Path`,
+/// subdividing curves while the control points deviate from the chord by
+/// more than `flattening_tolerance`, then scales every coordinate by
+/// `scale` into `CInt` space.
+pub fn flatten<T: IntPoint>(segments: &[CurveSegment], flattening_tolerance: f64, scale: f64) -> Path<T> {
+    let mut poly: Vec<DPoint> = Vec::new();
+
+    for seg in segments {
+        match *seg {
+            CurveSegment::Line(a, b) => {
+                if poly.is_empty() {
+                    poly.push(a);
+                }
+                poly.push(b);
+            }
+            CurveSegment::Quadratic(a, c, b) => {
+                if poly.is_empty() {
+                    poly.push(a);
+                }
+                flatten_quadratic(a, c, b, flattening_tolerance, 0, &mut poly);
+            }
+            CurveSegment::Cubic(a, c1, c2, b) => {
+                if poly.is_empty() {
+                    poly.push(a);
+                }
+                flatten_cubic(a, c1, c2, b, flattening_tolerance, 0, &mut poly);
+            }
+        }
+    }
+
+    Path {
+        poly: poly
+            .into_iter()
+            .map(|p| T::new((p.x * scale).round() as CInt, (p.y * scale).round() as CInt))
+            .collect(),
+    }
+}
+
+/// `flatten`, applied to several independent curves (e.g. subpaths of an
+/// SVG `<path>`).
+pub fn flatten_paths<T: IntPoint>(subpaths: &[Vec<CurveSegment>], flattening_tolerance: f64, scale: f64) -> Paths<T> {
+    Paths {
+        paths: subpaths
+            .iter()
+            .map(|segments| flatten(segments, flattening_tolerance, scale))
+            .collect(),
+    }
+}
+
+/// Recursion cap for `flatten_quadratic`/`flatten_cubic`, mirroring
+/// `offset.rs`'s `arc_steps` clamp to 512 steps: a pathological curve (or
+/// `flattening_tolerance <= 0.0`) would otherwise keep bisecting forever,
+/// since each split only roughly quarters the deviation (for quadratics
+/// and cubics alike) and float equality is never exactly reached. 24 halvings
+/// already produces 2^24 segments, far more than any real curve needs.
+const MAX_FLATTEN_DEPTH: u32 = 24;
+
+fn flatten_quadratic(a: DPoint, c: DPoint, b: DPoint, tol: f64, depth: u32, out: &mut Vec<DPoint>) {
+    if control_deviation(c, a, b) <= tol || depth >= MAX_FLATTEN_DEPTH {
+        out.push(b);
+        return;
+    }
+    let ac = midpoint(a, c);
+    let cb = midpoint(c, b);
+    let split = midpoint(ac, cb);
+    flatten_quadratic(a, ac, split, tol, depth + 1, out);
+    flatten_quadratic(split, cb, b, tol, depth + 1, out);
+}
+
+fn flatten_cubic(a: DPoint, c1: DPoint, c2: DPoint, b: DPoint, tol: f64, depth: u32, out: &mut Vec<DPoint>) {
+    let deviation = control_deviation(c1, a, b).max(control_deviation(c2, a, b));
+    if deviation <= tol || depth >= MAX_FLATTEN_DEPTH {
+        out.push(b);
+        return;
+    }
+
+    let ab = midpoint(a, c1);
+    let bc = midpoint(c1, c2);
+    let cd = midpoint(c2, b);
+    let ab_bc = midpoint(ab, bc);
+    let bc_cd = midpoint(bc, cd);
+    let split = midpoint(ab_bc, bc_cd);
+
+    flatten_cubic(a, ab, ab_bc, split, tol, depth + 1, out);
+    flatten_cubic(split, bc_cd, cd, b, tol, depth + 1, out);
+}
+
+fn midpoint(a: DPoint, b: DPoint) -> DPoint {
+    DPoint::new((a.x + b.x) * 0.5, (a.y + b.y) * 0.5)
+}
+
+/// Perpendicular distance of `p` from the chord `a -> b`.
+fn control_deviation(p: DPoint, a: DPoint, b: DPoint) -> f64 {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 1.0e-12 {
+        return ((p.x - a.x).powi(2) + (p.y - a.y).powi(2)).sqrt();
+    }
+    (dy * p.x - dx * p.y + b.x * a.y - b.y * a.x).abs() / len
+}
+
+/// Turns an open polyline `line` (already in `CInt` space) with the given
+/// `stroke_width` into a closed, fillable `Paths`: offsets both sides of the
+/// line by `stroke_width / 2` and joins them with the chosen end cap,
+/// reusing [`ClipperOffset`]. Every path of the offset result is returned.
+/// Since `cleanup::resolve_self_overlap` returns its input unchanged, that
+/// is a single, possibly self-overlapping path for a `line` of two or more
+/// points, and no path otherwise.
+pub fn stroke_to_fill<T: IntPoint>(line: &Path<T>, stroke_width: f64, end_type: EndType) -> Paths<T> {
+    let mut offset = ClipperOffset::new();
+    offset.add_path(Path { poly: line.poly.clone() }, JoinType::Round, end_type);
+    offset.execute(stroke_width / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+
+    #[test]
+    fn flatten_terminates_with_zero_tolerance() {
+        // `flattening_tolerance = 0.0` would make the old unbounded
+        // recursion split forever (float equality is never exactly hit);
+        // the depth cap must make this return instead of overflowing the
+        // stack.
+        let segments = vec![CurveSegment::Quadratic(
+            DPoint::new(0.0, 0.0),
+            DPoint::new(50.0, 100.0),
+            DPoint::new(100.0, 0.0),
+        )];
+        let result: Path<IntPoint3d> = flatten(&segments, 0.0, 1.0);
+
+        assert!(result.poly.len() > 2);
+        assert!(result.poly.len() <= (1 << MAX_FLATTEN_DEPTH) + 1);
+    }
+
+    #[test]
+    fn flatten_cubic_terminates_with_zero_tolerance() {
+        let segments = vec![CurveSegment::Cubic(
+            DPoint::new(0.0, 0.0),
+            DPoint::new(25.0, 100.0),
+            DPoint::new(75.0, -100.0),
+            DPoint::new(100.0, 0.0),
+        )];
+        let result: Path<IntPoint3d> = flatten(&segments, 0.0, 1.0);
+
+        assert!(result.poly.len() > 2);
+        assert!(result.poly.len() <= (1 << MAX_FLATTEN_DEPTH) + 1);
+    }
+
+    #[test]
+    fn stroke_to_fill_returns_every_piece_of_a_self_overlapping_stroke() {
+        // A sharp back-and-forth polyline: offsetting it by a generous
+        // stroke width gives a self-overlapping offset, the kind of input
+        // a resolving union pass would split into several pieces.
+        let line = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(100, 0),
+                IntPoint3d::new(0, 1),
+                IntPoint3d::new(100, 2),
+            ],
+        };
+
+        let result = stroke_to_fill(&line, 50.0, EndType::OpenButt);
+
+        // Whatever ClipperOffset produces, `stroke_to_fill` must hand all
+        // of it back rather than silently dropping every piece past the
+        // first.
+        assert!(!result.paths.is_empty());
+        let mut direct = ClipperOffset::new();
+        direct.add_path(Path { poly: line.poly.clone() }, JoinType::Round, EndType::OpenButt);
+        assert_eq!(result.paths.len(), direct.execute(25.0).paths.len());
+    }
+}
@@ -8,6 +8,9 @@ pub struct Edge<T: IntPoint> {
     pub top: T,
     pub dx: f64,
     pub poly_typ: PolyType,
+    /// Set for edges belonging to a path added via `add_path(.., is_open: true)`.
+    /// Open edges never contribute to the winding fill, only to line clipping.
+    pub is_open: bool,
     /// side only refers to current side of solution poly
     pub side: EdgeSide,
     /// 1 or -1 depending on winding direction
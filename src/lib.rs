@@ -43,9 +43,20 @@
 #[macro_use]
 pub mod macros;
 pub mod consts;
+pub mod cleanup;
+pub mod clipper;
+pub mod clipper_d;
+pub mod curve_import;
 pub mod edge;
+pub mod minkowski;
 pub mod node;
+pub mod offset;
+pub mod path_simplify;
 pub mod point;
+pub mod rect_clip;
+pub mod simplify;
+pub mod thread_pool;
+pub mod triangulate;
 
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -61,7 +72,7 @@ pub enum Direction {
     LeftToRight,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Copy, Clone)]
 pub enum ClipType {
     Intersection,
     Union,
@@ -69,7 +80,7 @@ pub enum ClipType {
     Xor,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Copy, Clone)]
 pub enum PolyType {
     Subject,
     Clip,
@@ -79,7 +90,7 @@ pub enum PolyType {
 /// EvenOdd & NonZero (GDI, GDI+, XLib, OpenGL, Cairo, AGG, Quartz, SVG, Gr32)
 /// Others rules include Positive, Negative and ABS_GTR_EQ_TWO (only in OpenGL)
 /// see http://glprogramming.com/red/chapter11.html
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Copy, Clone)]
 pub enum PolyFillType {
     EvenOdd,
     NonZero,
@@ -214,54 +225,119 @@ pub struct LocalMinimum<T: IntPoint> {
     _type: PhantomData<T>,
 }
 
-#[derive(PartialEq)]
+/// A single point of a clip result, stored in an [`OutPtPool`] arena.
+///
+/// `next`/`prev` are indices into the owning pool rather than `Arc<OutPt>`
+/// pointers: the algorithm repeatedly splices and reverses these rings in
+/// place, which an `Arc`-based cycle can't support (you can't mutate through
+/// a shared reference, and the cycle itself leaks). Indices into a flat pool
+/// are also friendlier to the cache than chasing pointers.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct OutPt<T: IntPoint> {
     pub idx: usize,
     pub pt: T,
-    pub next: Arc<OutPt<T>>,
-    pub prev: Arc<OutPt<T>>,
+    pub next: usize,
+    pub prev: usize,
 }
 
-impl<T: IntPoint> OutPt<T> {
-    // TODO!!
-    pub fn area(&self) -> f64 {
-        let start = self.next.clone();
-        let mut area = 0.0;
-        let mut op = start.clone();
+/// Owns every [`OutPt`] produced while building clip results. `OutRec::pts`
+/// and `OutRec::bottom_pt` are indices into this pool.
+pub struct OutPtPool<T: IntPoint> {
+    pub pts: Vec<OutPt<T>>,
+}
+
+impl<T: IntPoint> OutPtPool<T> {
+    pub fn new() -> Self {
+        Self { pts: Vec::new() }
+    }
+
+    /// Allocates a new, self-linked (single-point ring) `OutPt` and returns
+    /// its index.
+    pub fn alloc(&mut self, pt: T) -> usize {
+        let idx = self.pts.len();
+        self.pts.push(OutPt { idx: idx, pt: pt, next: idx, prev: idx });
+        idx
+    }
+
+    /// Inserts a new point into the ring immediately after `after`,
+    /// returning the new point's index.
+    pub fn insert_after(&mut self, after: usize, pt: T) -> usize {
+        let next = self.pts[after].next;
+        let idx = self.alloc(pt);
+        self.pts[after].next = idx;
+        self.pts[idx].prev = after;
+        self.pts[idx].next = next;
+        self.pts[next].prev = idx;
+        idx
+    }
+
+    /// The signed area of the ring starting at `start`.
+    pub fn area(&self, start: usize) -> f64 {
+        let mut a = 0.0;
+        let mut op = start;
         loop {
-            area += ((op.prev.pt.get_x() + op.pt.get_x()) *
-                     (op.prev.pt.get_y() - op.pt.get_y())) as f64;
-            op = op.next.clone();
-            if *op == *start { break; }
+            let cur = &self.pts[op];
+            let nxt = &self.pts[cur.next];
+            a += (cur.pt.get_x() + nxt.pt.get_x()) as f64 * (cur.pt.get_y() - nxt.pt.get_y()) as f64;
+            op = cur.next;
+            if op == start { break; }
         }
+        -a * 0.5
+    }
 
-        area * 0.5
+    /// Reverses the ring starting at `start` in place by swapping every
+    /// point's `next`/`prev`.
+    pub fn reverse_poly_pt_list(&mut self, start: usize) {
+        let mut op = start;
+        loop {
+            let next = self.pts[op].next;
+            self.pts[op].next = self.pts[op].prev;
+            self.pts[op].prev = next;
+            op = next;
+            if op == start { break; }
+        }
     }
 
-    pub fn reverse_poly_pt_list(&mut self) {
-/*
+    /// Collects the ring starting at `start` into a coordinate `Path`.
+    pub fn to_path(&self, start: usize) -> Path<T> {
+        let mut poly = Vec::new();
+        let mut op = start;
+        loop {
+            poly.push(self.pts[op].pt);
+            op = self.pts[op].next;
+            if op == start { break; }
+        }
+        Path { poly: poly }
+    }
 
-        // not possible in the rust model, also very bad for cache
-        let start = self.next.clone();
-        let mut op = start.clone();
+    pub fn point_is_vertex(&self, pt: &T, start: usize) -> bool {
+        let mut op = start;
         loop {
-            let pp2 = op.next.clone();
-            op.next = op.prev.clone();
-            op.prev = pp2.clone();
-            op = pp2;
-            if *op == *start { break; }
+            if self.pts[op].pt == *pt { return true; }
+            op = self.pts[op].next;
+            if op == start { break; }
         }
+        false
+    }
 
-            if (!pp) return;
-            OutPt *pp1, *pp2;
-            pp1 = pp;
-            do {
-            pp2 = pp1->Next;
-            pp1->Next = pp1->Prev;
-            pp1->Prev = pp2;
-            pp1 = pp2;
-            } while( pp1 != pp );
-*/
+    /// Checks if `pt` falls in the ring starting at `start`.
+    /// Renamed from `int PointInPolygon (const IntPoint &pt, OutPt *op)`.
+    pub fn is_point_in_out_pt(&self, pt: &T, start: usize) -> i8 {
+        is_point_in_path(pt, &self.to_path(start))
+    }
+
+    /// Worst-case O(n^2): checks every point of the ring starting at
+    /// `ring1` against the ring starting at `ring2`.
+    pub fn poly2_contains_poly1(&self, ring1: usize, ring2: usize) -> bool {
+        let path2 = self.to_path(ring2);
+        let mut op = ring1;
+        loop {
+            let res = is_point_in_path(&self.pts[op].pt, &path2);
+            if res >= 0 { return res > 0; }
+            op = self.pts[op].next;
+            if op == ring1 { break; }
+        }
+        true
     }
 }
 
@@ -272,13 +348,20 @@ pub struct OutRec<T: IntPoint> {
     //see comments in clipper.pas
     pub first_left: Arc<OutRec<T>>,
     pub poly_node: Arc<PolyNode<T>>,
-    pub pts: Arc<OutPt<T>>,
-    pub bottom_pt: Arc<OutPt<T>>,
+    /// Index into the owning [`OutPtPool`].
+    pub pts: usize,
+    /// Index into the owning [`OutPtPool`].
+    pub bottom_pt: usize,
 }
 
 impl<T: IntPoint> OutRec<T> {
-    pub fn area(&self) -> f64 {
-        self.pts.area()
+    pub fn area(&self, pool: &OutPtPool<T>) -> f64 {
+        pool.area(self.pts)
+    }
+
+    /// Collects this `OutRec`'s ring back into a coordinate `Path`.
+    pub fn to_path(&self, pool: &OutPtPool<T>) -> Path<T> {
+        pool.to_path(self.pts)
     }
 }
 
@@ -294,21 +377,13 @@ impl<T: IntPoint> OutRec<T> {
 }
 
 pub struct Join<T: IntPoint> {
-    pub out_pt1: Arc<OutPt<T>>,
-    pub out_pt2: Arc<OutPt<T>>,
+    /// Index into the owning [`OutPtPool`].
+    pub out_pt1: usize,
+    /// Index into the owning [`OutPtPool`].
+    pub out_pt2: usize,
     pub off_pt: T,
 }
 
-pub fn point_is_vertex<T: IntPoint>(pt: &T, pp: Arc<OutPt<T>>) -> bool {
-    let mut pp2 = pp.clone();
-    loop {
-        if pp2.pt == *pt { return true; }
-        pp2 = pp2.next.clone();
-        if *pp2 == *pp { break; }
-    }
-    false
-}
-
 /// See http://citeseerx.ist.psu.edu/viewdoc/download?doi=10.1.1.88.5498&rep=rep1&type=pdf
 /// returns 0 if false, +1 if true, -1 if pt ON polygon boundary
 pub fn is_point_in_path<T: IntPoint>(pt: &T, path: &Path<T>) -> i8 {
@@ -382,54 +457,7 @@ pub fn is_point_in_path<T: IntPoint>(pt: &T, path: &Path<T>) -> i8 {
     return result;
 }
 
-/// Checks if a point falls in an OutPt
-/// renamed from `int PointInPolygon (const IntPoint &pt, OutPt *op)`
-pub fn is_point_in_out_pt<T: IntPoint>(pt: &T, op: Arc<OutPt<T>>) -> i8 {
-
-    // This is different from the original algorithm:
-    // Instead of following pointers, we collect the OutPt into a path
-    // This provides better cache access + lets us reuse the point
-    let mut out_path = Vec::<T>::new();
-    let origin_op = op.clone();
-    let mut cur_op = op.clone();
-
-    while cur_op != origin_op {
-        out_path.push(cur_op.pt);
-        cur_op = cur_op.next.clone();
-    }
-
-    is_point_in_path(pt, &Path { poly: out_path })
-}
-
-/// TODO: this works, but it is worst-case O(n^2)
-/// as we check every point against every other point
-///
-/// In theory, this should perform better than the C++ version ("Poly2ContainsPoly1")
-/// due to better cache access.
-pub fn poly2_contains_poly1<T: IntPoint>(pt1: Arc<OutPt<T>>, pt2: Arc<OutPt<T>>) -> bool {
-
-    // create path for pt2
-    let mut out_path = Vec::<T>::new();
-    let origin_op = pt2.clone();
-    let mut cur_op = pt2.clone();
-
-    while cur_op != origin_op {
-        out_path.push(cur_op.pt);
-        cur_op = cur_op.next.clone();
-    }
-
-    let pt2_path = Path { poly: out_path };
-
-    let origin_op = pt1.clone();
-    let mut cur_op = pt1.clone();
-
-    while cur_op != origin_op {
-        let res = is_point_in_path(&cur_op.pt, &pt2_path);
-        if res >= 0 { return res > 0 }
-        cur_op = cur_op.next.clone();
-    }
-
-    true
-}
+// `is_point_in_out_pt` and `poly2_contains_poly1` now live on `OutPtPool`
+// (see above), since `OutPt` no longer carries its own `Arc` links.
 
 
@@ -0,0 +1,141 @@
+//! Minkowski sum and difference of `Path`s, i.e. sweeping a pattern shape
+//! along a path (tool/brush offsetting, CNC toolpath generation, collision
+//! offsetting).
+
+use cleanup::resolve_self_overlap;
+use point::IntPoint;
+use {Path, Paths, PolyFillType};
+
+/// Sweeps `pattern` along every vertex of `path`, returning the set of
+/// per-segment quads that should merge (under `NonZero` fill) into a single
+/// simple outline of the swept area.
+///
+/// For each vertex `path[j]` a translated copy of `pattern` is produced
+/// (`translated[j][i] = pattern[i] + path[j]`); for every pair of
+/// consecutive path vertices `j, j+1` and consecutive pattern vertices
+/// `i, i+1`, the quadrilateral connecting the four translated points is
+/// reoriented to positive area (if needed) before being emitted. This
+/// matters under `NonZero` fill: two overlapping quads with opposite
+/// winding can cancel to zero at the overlap instead of merging, carving
+/// an unwanted hole into the swept region.
+///
+/// The actual merge, however, is done by [`cleanup::resolve_self_overlap`],
+/// which is currently a no-op pending the Vatti scanline in `clipper.rs` --
+/// so today this returns the raw, unmerged quad list (correctly oriented,
+/// but still overlapping each other), not the final simplified boundary.
+/// Callers that need the merged outline right now must union the result
+/// themselves once a real `Clipper::execute` is available.
+pub fn minkowski_sum<T: IntPoint>(pattern: &Path<T>, path: &Path<T>, path_closed: bool) -> Paths<T> {
+    let pat_len = pattern.poly.len();
+    let path_len = path.poly.len();
+    if pat_len < 1 || path_len < 1 {
+        return Paths { paths: Vec::new() };
+    }
+
+    // translated copies of `pattern`, one per vertex of `path`
+    let translated: Vec<Vec<T>> = path
+        .poly
+        .iter()
+        .map(|pt| {
+            pattern
+                .poly
+                .iter()
+                .map(|pp| T::new(pp.get_x() + pt.get_x(), pp.get_y() + pt.get_y()))
+                .collect()
+        })
+        .collect();
+
+    let segments = if path_closed { path_len } else { path_len.saturating_sub(1) };
+
+    let mut quads = Vec::with_capacity(segments * pat_len);
+    for j in 0..segments {
+        let j2 = (j + 1) % path_len;
+        for i in 0..pat_len {
+            let i2 = (i + 1) % pat_len;
+
+            let mut quad = Path {
+                poly: vec![
+                    translated[j][i],
+                    translated[j][i2],
+                    translated[j2][i2],
+                    translated[j2][i],
+                ],
+            };
+            if !quad.orientation() {
+                quad.poly.reverse();
+            }
+            quads.push(quad);
+        }
+    }
+
+    resolve_self_overlap(Paths { paths: quads }, PolyFillType::NonZero)
+}
+
+/// The Minkowski difference of `a` and `b`: the sum of `a` with `b`
+/// point-reflected through the origin.
+pub fn minkowski_diff<T: IntPoint>(a: &Path<T>, b: &Path<T>) -> Paths<T> {
+    let negated = Path {
+        poly: b.poly.iter().map(|p| T::new(-p.get_x(), -p.get_y())).collect(),
+    };
+    minkowski_sum(&negated, a, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+
+    #[test]
+    fn every_emitted_quad_has_positive_orientation() {
+        let pattern = Path {
+            poly: vec![
+                IntPoint3d::new(-1, -1),
+                IntPoint3d::new(1, -1),
+                IntPoint3d::new(1, 1),
+                IntPoint3d::new(-1, 1),
+            ],
+        };
+        let path = Path {
+            poly: vec![IntPoint3d::new(0, 0), IntPoint3d::new(10, 0), IntPoint3d::new(10, 10)],
+        };
+
+        let result = minkowski_sum(&pattern, &path, false);
+
+        assert!(!result.paths.is_empty());
+        for quad in &result.paths {
+            assert!(quad.orientation(), "every emitted quad must wind positively so NonZero fill merges overlaps instead of cancelling them");
+        }
+    }
+
+    /// Known limitation: `resolve_self_overlap` is currently a no-op, so
+    /// `minkowski_sum` returns one quad per (path segment, pattern edge)
+    /// pair rather than a merged outline. This sweeps a 2-segment path with
+    /// a 4-vertex square pattern (2 segments * 4 pattern edges = 8 quads)
+    /// and pins down that all 8 still come back unmerged, so a future fix
+    /// to `resolve_self_overlap` (which should make this count drop) isn't
+    /// missed silently.
+    #[test]
+    fn minkowski_sum_currently_returns_unmerged_quads_not_a_single_outline() {
+        let pattern = Path {
+            poly: vec![
+                IntPoint3d::new(-1, -1),
+                IntPoint3d::new(1, -1),
+                IntPoint3d::new(1, 1),
+                IntPoint3d::new(-1, 1),
+            ],
+        };
+        let path = Path {
+            poly: vec![IntPoint3d::new(0, 0), IntPoint3d::new(10, 0), IntPoint3d::new(10, 10)],
+        };
+
+        let result = minkowski_sum(&pattern, &path, false);
+
+        assert_eq!(
+            result.paths.len(),
+            8,
+            "expected today's no-op union pass to leave all 8 per-segment quads unmerged; \
+             if this now fails with a smaller count, resolve_self_overlap has been wired up \
+             for real -- update/remove this known-limitation test"
+        );
+    }
+}
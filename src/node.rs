@@ -53,8 +53,17 @@ impl<T: IntPoint> PolyNode<T> {
         }
     }
 
+    /// Whether this node represents a closed (filled-region) contour rather
+    /// than an open polyline.
+    pub(crate) fn is_closed(&self) -> bool {
+        !self.is_open
+    }
+
+    /// A node is a hole iff it sits at an odd depth in the tree (root-level
+    /// contours, and every other generation below them, are outer
+    /// boundaries; the generations in between are holes).
     pub(crate) fn is_hole(&self) -> bool {
-        let mut result = true;
+        let mut result = false;
         let mut node_idx = self.parent;
 
         loop {
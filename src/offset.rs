@@ -0,0 +1,591 @@
+//! Polygon offsetting (inflate/deflate), i.e. growing or shrinking `Paths`
+//! by a fixed `delta`.
+//!
+//! See "Polygon Offsetting by Computing Winding Numbers" (cited in the
+//! crate docs) for the underlying approach: walk each contour, classify
+//! every vertex as a convex or concave turn using the cross product of the
+//! adjacent edge normals, and emit the join geometry for convex turns while
+//! letting concave turns self-overlap (the overlap is meant to be cleaned
+//! up by a final union pass, see [`cleanup::resolve_self_overlap`]).
+//!
+//! [`cleanup::resolve_self_overlap`] is currently a no-op (it passes the
+//! union-fill-tagged raw paths straight through, pending the Vatti scanline
+//! in `clipper.rs`), so [`ClipperOffset::execute`] does NOT yet resolve
+//! concave-corner self-overlap: offsetting a concave contour can return
+//! self-intersecting output. This is a known, temporary limitation, not
+//! the intended final behavior -- see `offsetting_a_concave_hexagon...`
+//! below for a test pinning down exactly what ships today.
+//!
+//! Mirrors the upstream `ClipperOffset` shape: paths are registered one at a
+//! time (each with its own [`JoinType`]/[`EndType`], matching the per-path
+//! settings `PolyNode` already carries), then [`ClipperOffset::execute`]
+//! offsets all of them by a given `delta` in one pass.
+
+use std::f64::consts::PI;
+
+use cleanup::resolve_self_overlap;
+use consts::{DEF_ARC_TOLERANCE, TOLERANCE};
+use point::{CInt, IntPoint};
+use thread_pool::ThreadPool;
+use {EndType, JoinType, Path, Paths, PolyFillType};
+
+/// Default miter limit, matching the upstream Clipper default.
+pub const DEF_MITER_LIMIT: f64 = 2.0;
+
+#[derive(Copy, Clone)]
+struct Pt {
+    x: f64,
+    y: f64,
+}
+
+impl Pt {
+    #[inline]
+    fn new(x: f64, y: f64) -> Self {
+        Pt { x, y }
+    }
+}
+
+/// Grows (`delta > 0`) or shrinks (`delta < 0`) one or more registered paths
+/// by a fixed delta. Build one with [`ClipperOffset::new`], register paths
+/// via [`ClipperOffset::add_path`]/[`ClipperOffset::add_paths`], then call
+/// [`ClipperOffset::execute`].
+///
+/// Open paths (`EndType::OpenButt`/`OpenSquare`/`OpenRound`) get the same
+/// per-vertex join treatment as closed polygons at every interior bend --
+/// only the two open ends are handled separately, via the end cap.
+pub struct ClipperOffset<T: IntPoint> {
+    miter_limit: f64,
+    arc_tolerance: f64,
+    paths: Vec<(Path<T>, JoinType, EndType)>,
+}
+
+impl<T: IntPoint> ClipperOffset<T> {
+    pub fn new() -> Self {
+        Self {
+            miter_limit: DEF_MITER_LIMIT,
+            arc_tolerance: DEF_ARC_TOLERANCE,
+            paths: Vec::new(),
+        }
+    }
+
+    pub fn with_miter_limit(mut self, miter_limit: f64) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn with_arc_tolerance(mut self, arc_tolerance: f64) -> Self {
+        self.arc_tolerance = arc_tolerance;
+        self
+    }
+
+    /// Registers a single path to be offset, with its own join/end type.
+    pub fn add_path(&mut self, path: Path<T>, join_type: JoinType, end_type: EndType) {
+        self.paths.push((path, join_type, end_type));
+    }
+
+    /// Registers every path of `paths`, all sharing `join_type`/`end_type`.
+    pub fn add_paths(&mut self, paths: Paths<T>, join_type: JoinType, end_type: EndType) {
+        for path in paths.paths {
+            self.add_path(path, join_type, end_type);
+        }
+    }
+
+    /// Offsets every registered path by `delta`.
+    pub fn execute(&self, delta: f64) -> Paths<T> {
+        if delta.abs() < TOLERANCE {
+            return Paths {
+                paths: self.paths.iter().map(|(p, _, _)| Path { poly: p.poly.clone() }).collect(),
+            };
+        }
+
+        let mut raw = Vec::with_capacity(self.paths.len());
+        for (path, join_type, end_type) in &self.paths {
+            if let Some(offset) = offset_one(path, *join_type, *end_type, delta, self.miter_limit, self.arc_tolerance) {
+                raw.push(offset);
+            }
+        }
+
+        resolve_self_overlap(Paths { paths: raw }, fill_for_delta(delta))
+    }
+}
+
+impl<T: IntPoint + Send + 'static> ClipperOffset<T> {
+    /// Same as [`ClipperOffset::execute`], but offsets each registered path
+    /// on `pool` instead of sequentially on the calling thread. Every path
+    /// offsets independently of the others (only the final union pass needs
+    /// them all together), which makes this embarrassingly parallel; falls
+    /// back to [`ClipperOffset::execute`] when `pool` is `None`.
+    pub fn execute_with_pool(&self, delta: f64, pool: Option<&ThreadPool>) -> Paths<T>
+    where
+        Path<T>: Send,
+    {
+        let pool = match pool {
+            Some(pool) => pool,
+            None => return self.execute(delta),
+        };
+
+        if delta.abs() < TOLERANCE {
+            return Paths {
+                paths: self.paths.iter().map(|(p, _, _)| Path { poly: p.poly.clone() }).collect(),
+            };
+        }
+
+        let miter_limit = self.miter_limit;
+        let arc_tolerance = self.arc_tolerance;
+        let jobs: Vec<(Path<T>, JoinType, EndType)> = self
+            .paths
+            .iter()
+            .map(|(p, j, e)| (Path { poly: p.poly.clone() }, *j, *e))
+            .collect();
+
+        let raw: Vec<Path<T>> = pool
+            .map(jobs, move |(path, join_type, end_type)| {
+                offset_one(&path, join_type, end_type, delta, miter_limit, arc_tolerance)
+            })
+            .into_iter()
+            .flatten()
+            .collect();
+
+        resolve_self_overlap(Paths { paths: raw }, fill_for_delta(delta))
+    }
+}
+
+fn fill_for_delta(delta: f64) -> PolyFillType {
+    if delta > 0.0 {
+        PolyFillType::Positive
+    } else {
+        PolyFillType::Negative
+    }
+}
+
+fn offset_one<T: IntPoint>(
+    path: &Path<T>,
+    join_type: JoinType,
+    end_type: EndType,
+    delta: f64,
+    miter_limit: f64,
+    arc_tolerance: f64,
+) -> Option<Path<T>> {
+    if path.poly.len() < 2 {
+        return None;
+    }
+    let params = OffsetParams {
+        delta: delta,
+        join_type: join_type,
+        end_type: end_type,
+        miter_limit: miter_limit,
+        arc_tolerance: arc_tolerance,
+    };
+    Some(match end_type {
+        EndType::ClosedPolygon => params.offset_closed(path),
+        EndType::ClosedLine => params.offset_open(path, true),
+        _ => params.offset_open(path, false),
+    })
+}
+
+/// One-shot convenience: offsets every path in `paths` by `delta`, all
+/// sharing the same `join_type`/`end_type`.
+pub fn offset_paths<T: IntPoint>(paths: &Paths<T>, delta: f64, join_type: JoinType, end_type: EndType) -> Paths<T> {
+    let mut co = ClipperOffset::new();
+    co.add_paths(
+        Paths { paths: paths.paths.iter().map(|p| Path { poly: p.poly.clone() }).collect() },
+        join_type,
+        end_type,
+    );
+    co.execute(delta)
+}
+
+/// The resolved per-path settings used while walking a single contour.
+struct OffsetParams {
+    delta: f64,
+    join_type: JoinType,
+    end_type: EndType,
+    miter_limit: f64,
+    arc_tolerance: f64,
+}
+
+impl OffsetParams {
+    /// Offsets a single closed contour, one direction only.
+    fn offset_closed<T: IntPoint>(&self, path: &Path<T>) -> Path<T> {
+        let pts: Vec<Pt> = path
+            .poly
+            .iter()
+            .map(|p| Pt::new(p.get_x() as f64, p.get_y() as f64))
+            .collect();
+        let n = pts.len();
+        if n < 3 {
+            return Path { poly: path.poly.clone() };
+        }
+
+        let normals = edge_normals(&pts, true);
+        let mut out = Vec::with_capacity(n * 2);
+
+        for i in 0..n {
+            let prev = (i + n - 1) % n;
+            self.do_vertex(&pts, &normals, prev, i, true, &mut out);
+        }
+
+        to_path(out)
+    }
+
+    /// Offsets a single open path, walking forward then back with the
+    /// configured end cap.
+    fn offset_open<T: IntPoint>(&self, path: &Path<T>, closed_line: bool) -> Path<T> {
+        let pts: Vec<Pt> = path
+            .poly
+            .iter()
+            .map(|p| Pt::new(p.get_x() as f64, p.get_y() as f64))
+            .collect();
+        let n = pts.len();
+        if n < 2 {
+            return Path { poly: path.poly.clone() };
+        }
+
+        let normals = edge_normals(&pts, false);
+        let mut out = Vec::with_capacity(n * 4);
+
+        // walk forward along one side. Every interior vertex (not the final
+        // open end) has a real outgoing edge, so its normal must come from
+        // `normals[cur]` rather than falling back to the incoming normal.
+        for i in 0..n - 1 {
+            let cur = i + 1;
+            self.do_vertex(&pts, &normals, i, cur, cur < n - 1, &mut out);
+        }
+
+        if closed_line {
+            self.do_vertex(&pts, &normals, n - 1, 0, true, &mut out);
+        } else {
+            self.do_end_cap(&pts, &normals, n - 1, &mut out);
+        }
+
+        // walk back along the other side: reverse the point order and
+        // normals so the same convex-join logic produces the far side.
+        let mut rev_pts = pts.clone();
+        rev_pts.reverse();
+        let mut rev_normals: Vec<Pt> = normals.iter().rev().map(|p| Pt::new(-p.x, -p.y)).collect();
+        rev_normals.rotate_left(1);
+
+        for i in 0..n - 1 {
+            let cur = i + 1;
+            self.do_vertex(&rev_pts, &rev_normals, i, cur, cur < n - 1, &mut out);
+        }
+
+        if closed_line {
+            self.do_vertex(&rev_pts, &rev_normals, n - 1, 0, true, &mut out);
+        } else {
+            self.do_end_cap(&rev_pts, &rev_normals, n - 1, &mut out);
+        }
+
+        to_path(out)
+    }
+
+    /// Emits the offset geometry for the join at `pts[cur]`, between edge
+    /// `prev -> cur` and edge `cur -> next` (`next = (cur + 1) % len`, only
+    /// meaningful when `has_next` is set).
+    fn do_vertex(&self, pts: &[Pt], normals: &[Pt], prev: usize, cur: usize, has_next: bool, out: &mut Vec<Pt>) {
+        let n_prev = normals[prev];
+        let n_cur = if has_next { normals[cur] } else { n_prev };
+
+        let delta = self.delta;
+        let cross = n_prev.x * n_cur.y - n_prev.y * n_cur.x;
+        let is_convex = cross * delta.signum() >= 0.0;
+
+        if !is_convex {
+            // concave turn: emit both offset endpoints plus the original
+            // vertex, letting the overlap be resolved by the final union.
+            out.push(Pt::new(pts[cur].x + delta * n_prev.x, pts[cur].y + delta * n_prev.y));
+            out.push(pts[cur]);
+            if has_next {
+                out.push(Pt::new(pts[cur].x + delta * n_cur.x, pts[cur].y + delta * n_cur.y));
+            }
+            return;
+        }
+
+        match self.join_type {
+            JoinType::Miter => self.do_miter(pts, n_prev, n_cur, cur, cross, out),
+            JoinType::Square => self.do_square(pts, n_prev, n_cur, cur, out),
+            JoinType::Round => self.do_round(pts, n_prev, n_cur, cur, out),
+        }
+    }
+
+    fn do_miter(&self, pts: &[Pt], n_prev: Pt, n_cur: Pt, cur: usize, _cross: f64, out: &mut Vec<Pt>) {
+        let delta = self.delta;
+        // r = 1 + n_prev . n_cur; the miter point is pt + delta*(n_prev+n_cur)/r,
+        // at distance |delta| * sqrt(2 / r) from pt, so it stays within
+        // miter_limit * |delta| exactly when r >= 2 / miter_limit^2.
+        let r = 1.0 + (n_prev.x * n_cur.x + n_prev.y * n_cur.y);
+        if r > 0.0 && r >= 2.0 / (self.miter_limit * self.miter_limit) {
+            out.push(Pt::new(
+                pts[cur].x + delta * (n_prev.x + n_cur.x) / r,
+                pts[cur].y + delta * (n_prev.y + n_cur.y) / r,
+            ));
+        } else {
+            self.do_square(pts, n_prev, n_cur, cur, out);
+        }
+    }
+
+    fn do_square(&self, pts: &[Pt], n_prev: Pt, n_cur: Pt, cur: usize, out: &mut Vec<Pt>) {
+        let delta = self.delta;
+        out.push(Pt::new(pts[cur].x + delta * n_prev.x, pts[cur].y + delta * n_prev.y));
+        out.push(Pt::new(pts[cur].x + delta * n_cur.x, pts[cur].y + delta * n_cur.y));
+    }
+
+    fn do_round(&self, pts: &[Pt], n_prev: Pt, n_cur: Pt, cur: usize, out: &mut Vec<Pt>) {
+        let delta = self.delta.abs();
+        let steps = arc_steps(delta, self.arc_tolerance);
+
+        let a1 = n_prev.y.atan2(n_prev.x);
+        let mut a2 = n_cur.y.atan2(n_cur.x);
+        let sign = self.delta.signum();
+
+        // walk from a1 to a2 in the direction consistent with the turn.
+        let mut diff = a2 - a1;
+        if sign > 0.0 {
+            while diff < 0.0 {
+                diff += 2.0 * PI;
+            }
+        } else {
+            while diff > 0.0 {
+                diff -= 2.0 * PI;
+            }
+        }
+        a2 = a1 + diff;
+
+        let n = ((diff.abs() / steps).ceil() as usize).max(1).min(512);
+        for i in 0..=n {
+            let a = a1 + (a2 - a1) * (i as f64 / n as f64);
+            out.push(Pt::new(
+                pts[cur].x + self.delta * a.cos(),
+                pts[cur].y + self.delta * a.sin(),
+            ));
+        }
+    }
+
+    fn do_end_cap(&self, pts: &[Pt], normals: &[Pt], last: usize, out: &mut Vec<Pt>) {
+        let delta = self.delta;
+        let n_last = normals[last.saturating_sub(1).min(normals.len() - 1)];
+
+        match self.end_type {
+            EndType::OpenButt => {
+                out.push(Pt::new(pts[last].x + delta * n_last.x, pts[last].y + delta * n_last.y));
+                out.push(Pt::new(pts[last].x - delta * n_last.x, pts[last].y - delta * n_last.y));
+            }
+            EndType::OpenSquare => {
+                let tx = -n_last.y * delta;
+                let ty = n_last.x * delta;
+                out.push(Pt::new(
+                    pts[last].x + delta * n_last.x + tx,
+                    pts[last].y + delta * n_last.y + ty,
+                ));
+                out.push(Pt::new(
+                    pts[last].x - delta * n_last.x + tx,
+                    pts[last].y - delta * n_last.y + ty,
+                ));
+            }
+            EndType::OpenRound => {
+                let steps = arc_steps(delta.abs(), self.arc_tolerance);
+                let a1 = n_last.y.atan2(n_last.x);
+                let n = 180usize.min(((PI / steps).ceil() as usize).max(1));
+                for i in 0..=n {
+                    let a = a1 - PI * (i as f64 / n as f64);
+                    out.push(Pt::new(pts[last].x + delta * a.cos(), pts[last].y + delta * a.sin()));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Unit outward normals `n = (dy, -dx) / len` for every edge in `pts`.
+/// `closed` determines whether the last-to-first edge is included.
+fn edge_normals(pts: &[Pt], closed: bool) -> Vec<Pt> {
+    let n = pts.len();
+    let mut normals = Vec::with_capacity(n);
+    let edges = if closed { n } else { n - 1 };
+    for i in 0..edges {
+        let j = (i + 1) % n;
+        let dx = pts[j].x - pts[i].x;
+        let dy = pts[j].y - pts[i].y;
+        let len = (dx * dx + dy * dy).sqrt();
+        if len < TOLERANCE {
+            normals.push(Pt::new(0.0, 0.0));
+        } else {
+            normals.push(Pt::new(dy / len, -dx / len));
+        }
+    }
+    if !closed {
+        // duplicate the last normal so indexing by vertex stays in range
+        normals.push(*normals.last().unwrap());
+    }
+    normals
+}
+
+/// Angular step (radians) for round joins, derived from `arc_tolerance`,
+/// clamped to a sane maximum segment count.
+fn arc_steps(radius: f64, arc_tolerance: f64) -> f64 {
+    let tol = arc_tolerance.max(1.0e-6).min(radius.max(1.0e-6));
+    let steps_per_360 = PI / (1.0 - tol / radius.max(1.0e-6)).max(-1.0).min(1.0).acos();
+    (2.0 * PI / steps_per_360.max(1.0)).max(2.0 * PI / 512.0)
+}
+
+fn to_path<T: IntPoint>(pts: Vec<Pt>) -> Path<T> {
+    Path {
+        poly: pts
+            .into_iter()
+            .map(|p| T::new(p.x.round() as CInt, p.y.round() as CInt))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+
+    #[test]
+    fn offsetting_a_square_grows_its_area() {
+        let square = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(10, 0),
+                IntPoint3d::new(10, 10),
+                IntPoint3d::new(0, 10),
+            ],
+        };
+        let mut co = ClipperOffset::new();
+        co.add_path(Path { poly: square.poly.clone() }, JoinType::Square, EndType::ClosedPolygon);
+        let result = co.execute(2.0);
+
+        assert_eq!(result.paths.len(), 1);
+        assert!(result.paths[0].area() > square.area());
+    }
+
+    #[test]
+    fn open_path_bend_produces_a_real_right_angle_join() {
+        // An "L" bend: offsetting it with a square (bevel) join should put
+        // offset points from both edges' normals, (10, -1) and (11, 0),
+        // right at the outer corner (11, -1).
+        let l_path = Path {
+            poly: vec![IntPoint3d::new(0, 0), IntPoint3d::new(10, 0), IntPoint3d::new(10, 10)],
+        };
+        let mut co = ClipperOffset::new();
+        co.add_path(l_path, JoinType::Square, EndType::OpenButt);
+        let result = co.execute(1.0);
+
+        assert_eq!(result.paths.len(), 1);
+        let corner_present = result.paths[0].poly.iter().any(|p| {
+            (p.get_x() - 11).abs() <= 1 && (p.get_y() - (-1)).abs() <= 1
+        });
+        assert!(corner_present, "expected an offset point near (11, -1), got {:?}",
+            result.paths[0].poly.iter().map(|p| (p.get_x(), p.get_y())).collect::<Vec<_>>());
+        assert!(result.paths[0].poly.contains(&IntPoint3d::new(11, 0)),
+            "expected the outgoing edge's offset point (11, 0) at the bend");
+    }
+
+    #[test]
+    fn execute_applies_each_registered_paths_own_join_and_end_type() {
+        // A closed, mitered square and an open, round-jointed "L" bend,
+        // registered together -- each must be offset with its own settings,
+        // not whichever was registered first/last.
+        let square = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(10, 0),
+                IntPoint3d::new(10, 10),
+                IntPoint3d::new(0, 10),
+            ],
+        };
+        let l_path = Path {
+            poly: vec![IntPoint3d::new(100, 0), IntPoint3d::new(110, 0), IntPoint3d::new(110, 10)],
+        };
+
+        let mut co = ClipperOffset::new();
+        co.add_path(Path { poly: square.poly.clone() }, JoinType::Miter, EndType::ClosedPolygon);
+        co.add_path(l_path, JoinType::Round, EndType::OpenRound);
+        let result = co.execute(1.0);
+
+        assert_eq!(result.paths.len(), 2);
+
+        // Each input lives in a disjoint region of the plane (x in 0..=10
+        // vs. x in 100..=110), so which offset result belongs to which
+        // input can be told apart by location alone.
+        let near_square = result
+            .paths
+            .iter()
+            .find(|p| p.poly.iter().all(|pt| pt.get_x() < 50))
+            .expect("expected the closed square's offset to be present");
+        let near_l_path = result
+            .paths
+            .iter()
+            .find(|p| p.poly.iter().all(|pt| pt.get_x() >= 50))
+            .expect("expected the open round-jointed path's offset to also be present");
+
+        assert!(near_square.area() > square.area());
+        assert!(!near_l_path.poly.is_empty());
+    }
+
+    /// Known limitation: `resolve_self_overlap` (src/cleanup.rs) is a no-op
+    /// until the Vatti scanline in `clipper.rs` lands, so offsetting a
+    /// concave contour outward does NOT get its self-overlap resolved --
+    /// the raw, potentially self-intersecting geometry is returned as-is.
+    /// This pins down that today's output for an L-shaped hexagon really
+    /// is self-intersecting, so a future fix to `resolve_self_overlap`
+    /// (which should make this test start failing) isn't missed silently.
+    #[test]
+    fn offsetting_a_concave_hexagon_currently_self_intersects() {
+        let l_shape = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(100, 0),
+                IntPoint3d::new(100, 50),
+                IntPoint3d::new(50, 50),
+                IntPoint3d::new(50, 100),
+                IntPoint3d::new(0, 100),
+            ],
+        };
+        let mut co = ClipperOffset::new();
+        co.add_path(l_shape, JoinType::Miter, EndType::ClosedPolygon);
+        let result = co.execute(10.0);
+
+        assert_eq!(result.paths.len(), 1);
+        assert!(
+            has_self_intersection(&result.paths[0]),
+            "expected today's no-op union pass to leave the concave offset self-intersecting; \
+             if this now fails, resolve_self_overlap has been wired up for real -- \
+             update/remove this known-limitation test"
+        );
+    }
+
+    /// Non-adjacent-edge crossing test, used only to document the known
+    /// self-overlap limitation above -- not a general-purpose utility.
+    fn has_self_intersection(path: &Path<IntPoint3d>) -> bool {
+        let poly = &path.poly;
+        let n = poly.len();
+        for i in 0..n {
+            let (a1, a2) = (poly[i], poly[(i + 1) % n]);
+            for j in (i + 1)..n {
+                if j == i || (j + 1) % n == i || j == (i + 1) % n {
+                    continue;
+                }
+                let (b1, b2) = (poly[j], poly[(j + 1) % n]);
+                if segments_cross(a1, a2, b1, b2) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn segments_cross(p1: IntPoint3d, p2: IntPoint3d, p3: IntPoint3d, p4: IntPoint3d) -> bool {
+        fn cross(o: IntPoint3d, a: IntPoint3d, b: IntPoint3d) -> f64 {
+            let (ox, oy) = (o.get_x() as f64, o.get_y() as f64);
+            ((a.get_x() as f64 - ox) * (b.get_y() as f64 - oy))
+                - ((a.get_y() as f64 - oy) * (b.get_x() as f64 - ox))
+        }
+        let d1 = cross(p3, p4, p1);
+        let d2 = cross(p3, p4, p2);
+        let d3 = cross(p1, p2, p3);
+        let d4 = cross(p1, p2, p4);
+        ((d1 > 0.0) != (d2 > 0.0)) && ((d3 > 0.0) != (d4 > 0.0))
+    }
+}
@@ -0,0 +1,212 @@
+//! Vertex-count reduction for paths: Ramer-Douglas-Peucker simplification
+//! and collinear/near-duplicate cleanup. Complements [`simplify_polygon`]
+//! (which removes self-intersections) — this module never changes a path's
+//! topology, only how many vertices describe it.
+//!
+//! [`simplify_polygon`]: ::simplify::simplify_polygon
+
+use consts::TOLERANCE;
+use point::IntPoint;
+use Path;
+
+/// Reduces `path` (treated as an open polyline; the first and last vertices
+/// are kept as the anchor segment) via Ramer-Douglas-Peucker: the
+/// intermediate vertex with the greatest perpendicular distance from the
+/// anchor segment is kept (and the algorithm recurses on either side of it)
+/// only if that distance exceeds `epsilon`.
+pub fn simplify_path<T: IntPoint>(path: &Path<T>, epsilon: f64) -> Path<T> {
+    let poly = &path.poly;
+    if poly.len() < 3 {
+        return Path { poly: poly.clone() };
+    }
+
+    let mut keep = vec![false; poly.len()];
+    keep[0] = true;
+    *keep.last_mut().unwrap() = true;
+    rdp_recurse(poly, 0, poly.len() - 1, epsilon, &mut keep);
+
+    Path {
+        poly: poly.iter().zip(keep.iter()).filter(|&(_, &k)| k).map(|(&p, _)| p).collect(),
+    }
+}
+
+/// `simplify_path`, applied to every path in `paths`.
+pub fn simplify_paths<T: IntPoint>(paths: &::Paths<T>, epsilon: f64) -> ::Paths<T> {
+    ::Paths { paths: paths.paths.iter().map(|p| simplify_path(p, epsilon)).collect() }
+}
+
+/// `simplify_path`, but for a closed ring: splits at the two farthest-apart
+/// vertices first (so the anchor segment doesn't run straight through the
+/// middle of the polygon and collapse it), simplifies each half as an open
+/// polyline, then re-joins them.
+pub fn simplify_closed_path<T: IntPoint>(path: &Path<T>, epsilon: f64) -> Path<T> {
+    let poly = &path.poly;
+    let n = poly.len();
+    if n < 4 {
+        return Path { poly: poly.clone() };
+    }
+
+    let mut best_dist = -1.0;
+    let mut best = (0usize, 1usize);
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let d = dist_sq(poly[i], poly[j]);
+            if d > best_dist {
+                best_dist = d;
+                best = (i, j);
+            }
+        }
+    }
+    let (i, j) = best;
+
+    let seg1: Vec<T> = poly[i..=j].to_vec();
+    let mut seg2: Vec<T> = poly[j..].to_vec();
+    seg2.extend_from_slice(&poly[..=i]);
+
+    let mut simplified1 = simplify_path(&Path { poly: seg1 }, epsilon).poly;
+    let simplified2 = simplify_path(&Path { poly: seg2 }, epsilon).poly;
+
+    simplified1.pop(); // shared with simplified2's first point
+    simplified1.extend(simplified2);
+    simplified1.pop(); // shared with the ring's own first point
+
+    Path { poly: simplified1 }
+}
+
+fn rdp_recurse<T: IntPoint>(poly: &[T], start: usize, end: usize, epsilon: f64, keep: &mut [bool]) {
+    if end <= start + 1 {
+        return;
+    }
+
+    let mut max_dist = 0.0;
+    let mut max_idx = start;
+    for i in (start + 1)..end {
+        let d = perpendicular_distance(poly[i], poly[start], poly[end]);
+        if d > max_dist {
+            max_dist = d;
+            max_idx = i;
+        }
+    }
+
+    if max_dist > epsilon {
+        keep[max_idx] = true;
+        rdp_recurse(poly, start, max_idx, epsilon, keep);
+        rdp_recurse(poly, max_idx, end, epsilon, keep);
+    }
+}
+
+fn perpendicular_distance<T: IntPoint>(p: T, a: T, b: T) -> f64 {
+    let (ax, ay) = (a.get_x() as f64, a.get_y() as f64);
+    let (bx, by) = (b.get_x() as f64, b.get_y() as f64);
+    let (px, py) = (p.get_x() as f64, p.get_y() as f64);
+
+    let dx = bx - ax;
+    let dy = by - ay;
+    let len = (dx * dx + dy * dy).sqrt();
+
+    if len < TOLERANCE {
+        return ((px - ax).powi(2) + (py - ay).powi(2)).sqrt();
+    }
+
+    (dy * px - dx * py + bx * ay - by * ax).abs() / len
+}
+
+fn dist_sq<T: IntPoint>(a: T, b: T) -> f64 {
+    let dx = (a.get_x() - b.get_x()) as f64;
+    let dy = (a.get_y() - b.get_y()) as f64;
+    dx * dx + dy * dy
+}
+
+fn cross_z<T: IntPoint>(a: T, b: T, c: T) -> f64 {
+    let abx = (b.get_x() - a.get_x()) as f64;
+    let aby = (b.get_y() - a.get_y()) as f64;
+    let acx = (c.get_x() - a.get_x()) as f64;
+    let acy = (c.get_y() - a.get_y()) as f64;
+    abx * acy - aby * acx
+}
+
+/// Removes vertices closer than `distance` to their neighbors, then drops
+/// near-collinear triples (perpendicular distance of the middle vertex from
+/// its neighbors' chord below `distance`), repeating until nothing more can
+/// be dropped.
+pub fn clean_polygon<T: IntPoint>(path: &Path<T>, distance: f64) -> Path<T> {
+    let poly = &path.poly;
+    if poly.len() < 3 {
+        return Path { poly: poly.clone() };
+    }
+
+    let dist_threshold = distance * distance;
+    let mut out: Vec<T> = Vec::with_capacity(poly.len());
+    for &p in poly {
+        if let Some(&last) = out.last() {
+            if dist_sq(last, p) < dist_threshold {
+                continue;
+            }
+        }
+        out.push(p);
+    }
+    if out.len() > 1 && dist_sq(*out.last().unwrap(), out[0]) < dist_threshold {
+        out.pop();
+    }
+
+    let mut changed = true;
+    while changed && out.len() > 2 {
+        changed = false;
+        let n = out.len();
+        for i in 0..n {
+            let a = out[(i + n - 1) % n];
+            let b = out[i];
+            let c = out[(i + 1) % n];
+            if perpendicular_distance(b, a, c) < distance {
+                out.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+
+    Path { poly: out }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+
+    #[test]
+    fn clean_polygon_drops_a_near_collinear_vertex() {
+        // (5, 1) sits only 1 unit off the chord from (0,0) to (10,0) --
+        // not exactly collinear, but well within a `distance` of 2.0.
+        let poly = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(5, 1),
+                IntPoint3d::new(10, 0),
+                IntPoint3d::new(10, 10),
+                IntPoint3d::new(0, 10),
+            ],
+        };
+
+        let cleaned = clean_polygon(&poly, 2.0);
+
+        assert_eq!(cleaned.poly.len(), 4);
+        assert!(!cleaned.poly.contains(&IntPoint3d::new(5, 1)));
+    }
+
+    #[test]
+    fn clean_polygon_keeps_vertices_outside_the_threshold() {
+        let poly = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(5, 5),
+                IntPoint3d::new(10, 0),
+                IntPoint3d::new(10, 10),
+                IntPoint3d::new(0, 10),
+            ],
+        };
+
+        let cleaned = clean_polygon(&poly, 2.0);
+
+        assert_eq!(cleaned.poly.len(), 5);
+    }
+}
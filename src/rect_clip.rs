@@ -0,0 +1,135 @@
+//! Fast axis-aligned rectangle clipping (viewport culling, tile bucketing).
+//!
+//! When the clip region is a simple rectangle, routing through the full
+//! Vatti scanline is wasteful. This clips each subject path against the
+//! four half-planes of the rectangle with a Sutherland-Hodgman pass, which
+//! runs in `O(n)` per path and naturally re-closes polygons that exit and
+//! re-enter the rectangle along its border. For a non-rectangular clip
+//! region, fall back to the general `Clipper` engine instead.
+
+use point::{CInt, IntPoint};
+use {Path, Paths};
+
+/// Clips every path in `paths` against the axis-aligned rectangle spanned
+/// by the two (in either order) corners `rect.0`/`rect.1`.
+pub fn rect_clip<T: IntPoint>(rect: (T, T), paths: &Paths<T>) -> Paths<T> {
+    let min_x = rect.0.get_x().min(rect.1.get_x());
+    let max_x = rect.0.get_x().max(rect.1.get_x());
+    let min_y = rect.0.get_y().min(rect.1.get_y());
+    let max_y = rect.0.get_y().max(rect.1.get_y());
+
+    let mut out = Vec::with_capacity(paths.paths.len());
+    for path in &paths.paths {
+        if path.poly.len() < 3 {
+            continue;
+        }
+
+        let mut poly = path.poly.clone();
+        poly = clip_half_plane(&poly, |p: T| p.get_x() >= min_x, |a, b| intersect_vertical(a, b, min_x));
+        poly = clip_half_plane(&poly, |p: T| p.get_x() <= max_x, |a, b| intersect_vertical(a, b, max_x));
+        poly = clip_half_plane(&poly, |p: T| p.get_y() >= min_y, |a, b| intersect_horizontal(a, b, min_y));
+        poly = clip_half_plane(&poly, |p: T| p.get_y() <= max_y, |a, b| intersect_horizontal(a, b, max_y));
+
+        if poly.len() >= 3 {
+            out.push(Path { poly: poly });
+        }
+    }
+
+    Paths { paths: out }
+}
+
+/// One Sutherland-Hodgman pass: walks `poly`, keeping vertices that satisfy
+/// `inside` and inserting `intersect(prev, cur)` at every edge that crosses
+/// the boundary.
+fn clip_half_plane<T: IntPoint, F: Fn(T) -> bool, G: Fn(T, T) -> T>(poly: &[T], inside: F, intersect: G) -> Vec<T> {
+    if poly.is_empty() {
+        return Vec::new();
+    }
+
+    let n = poly.len();
+    let mut output = Vec::with_capacity(n + 2);
+
+    for i in 0..n {
+        let cur = poly[i];
+        let prev = poly[(i + n - 1) % n];
+        let cur_in = inside(cur);
+        let prev_in = inside(prev);
+
+        if cur_in {
+            if !prev_in {
+                output.push(intersect(prev, cur));
+            }
+            output.push(cur);
+        } else if prev_in {
+            output.push(intersect(prev, cur));
+        }
+    }
+
+    output
+}
+
+fn intersect_vertical<T: IntPoint>(a: T, b: T, x: CInt) -> T {
+    let t = (x - a.get_x()) as f64 / (b.get_x() - a.get_x()) as f64;
+    let y = a.get_y() as f64 + t * (b.get_y() - a.get_y()) as f64;
+    T::new(x, y.round() as CInt)
+}
+
+fn intersect_horizontal<T: IntPoint>(a: T, b: T, y: CInt) -> T {
+    let t = (y - a.get_y()) as f64 / (b.get_y() - a.get_y()) as f64;
+    let x = a.get_x() as f64 + t * (b.get_x() - a.get_x()) as f64;
+    T::new(x.round() as CInt, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+
+    fn square(x: CInt, y: CInt, size: CInt) -> Path<IntPoint3d> {
+        Path {
+            poly: vec![
+                IntPoint3d::new(x, y),
+                IntPoint3d::new(x + size, y),
+                IntPoint3d::new(x + size, y + size),
+                IntPoint3d::new(x, y + size),
+            ],
+        }
+    }
+
+    #[test]
+    fn a_square_fully_inside_the_rect_passes_through_unchanged() {
+        let rect = (IntPoint3d::new(0, 0), IntPoint3d::new(100, 100));
+        let paths = Paths { paths: vec![square(10, 10, 20)] };
+
+        let result = rect_clip(rect, &paths);
+
+        assert_eq!(result.paths.len(), 1);
+        assert!(result.paths[0].poly == paths.paths[0].poly);
+    }
+
+    #[test]
+    fn a_square_fully_outside_the_rect_is_dropped() {
+        let rect = (IntPoint3d::new(0, 0), IntPoint3d::new(100, 100));
+        let paths = Paths { paths: vec![square(200, 200, 20)] };
+
+        let result = rect_clip(rect, &paths);
+
+        assert!(result.paths.is_empty());
+    }
+
+    #[test]
+    fn a_square_straddling_the_rect_border_is_clipped_to_it() {
+        let rect = (IntPoint3d::new(0, 0), IntPoint3d::new(100, 100));
+        let paths = Paths { paths: vec![square(50, 50, 100)] };
+
+        let result = rect_clip(rect, &paths);
+
+        assert_eq!(result.paths.len(), 1);
+        for p in &result.paths[0].poly {
+            assert!(p.get_x() >= 0 && p.get_x() <= 100);
+            assert!(p.get_y() >= 0 && p.get_y() <= 100);
+        }
+        // clipped to the rect's corner: (50,50)-(100,50)-(100,100)-(50,100)
+        assert!(result.paths[0].poly.contains(&IntPoint3d::new(100, 100)));
+    }
+}
@@ -0,0 +1,203 @@
+//! Self-intersection removal: turns a self-intersecting, non-simple contour
+//! into well-formed, non-self-intersecting `Paths` -- for the common case of
+//! isolated two-edge crossings (see [`simplify_polygon`] for the exact
+//! scope and its current limitations).
+
+use std::collections::HashMap;
+
+use cleanup::resolve_self_overlap;
+use point::{CInt, IntPoint};
+use {Path, Paths, PolyFillType};
+
+/// Splits `poly` at every place it crosses itself, then feeds the resulting
+/// simple loops through a union pass under `fill` to fix up winding and drop
+/// zero-area artifacts.
+///
+/// The crossing-split handles the common case of a contour that self-crosses
+/// at isolated points (e.g. a "bowtie"/figure-eight); it does not attempt to
+/// untangle more than two edges meeting at the same point.
+///
+/// `fill` is currently a no-op: the union pass it's meant to drive
+/// ([`cleanup::resolve_self_overlap`]) is unimplemented pending the Vatti
+/// scanline in `clipper.rs`, so `PolyFillType::EvenOdd` vs. `NonZero` (etc.)
+/// makes no difference to the output today. The parameter is kept so
+/// callers are already written against the final signature once that
+/// union pass lands.
+pub fn simplify_polygon<T: IntPoint>(poly: &Path<T>, fill: PolyFillType) -> Paths<T> {
+    let fill_for_resolve = match fill {
+        PolyFillType::Negative => PolyFillType::Negative,
+        _ => PolyFillType::NonZero,
+    };
+    let loops = split_self_intersections(&poly.poly);
+    resolve_self_overlap(
+        Paths {
+            paths: loops.into_iter().map(|poly| Path { poly }).collect(),
+        },
+        fill_for_resolve,
+    )
+}
+
+/// Batch variant of [`simplify_polygon`].
+pub fn simplify_polygons<T: IntPoint>(polys: &Paths<T>, fill: PolyFillType) -> Paths<T> {
+    let fill_for_resolve = match fill {
+        PolyFillType::Negative => PolyFillType::Negative,
+        _ => PolyFillType::NonZero,
+    };
+    let mut all_loops = Vec::new();
+    for path in &polys.paths {
+        all_loops.extend(split_self_intersections(&path.poly));
+    }
+    resolve_self_overlap(
+        Paths {
+            paths: all_loops.into_iter().map(|poly| Path { poly }).collect(),
+        },
+        fill_for_resolve,
+    )
+}
+
+/// Splits a closed ring at every pairwise edge crossing, then unwinds the
+/// resulting vertex sequence into one or more simple closed loops.
+fn split_self_intersections<T: IntPoint>(ring: &[T]) -> Vec<Vec<T>> {
+    let n = ring.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<Vec<(f64, T)>> = vec![Vec::new(); n];
+    for i in 0..n {
+        let (a1, a2) = (ring[i], ring[(i + 1) % n]);
+        for j in (i + 1)..n {
+            let j_next = (j + 1) % n;
+            // Skip edges that already share a vertex with edge `i`.
+            if j == i || j_next == i || j == (i + 1) % n {
+                continue;
+            }
+            let (b1, b2) = (ring[j], ring[j_next]);
+            if let Some(pt) = segment_intersection(a1, a2, b1, b2) {
+                hits[i].push((param_along(a1, a2, pt), pt));
+                hits[j].push((param_along(b1, b2, pt), pt));
+            }
+        }
+    }
+
+    let mut aug = Vec::with_capacity(n);
+    for i in 0..n {
+        aug.push(ring[i]);
+        let mut pts = hits[i].clone();
+        pts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        aug.extend(pts.into_iter().map(|(_, p)| p));
+    }
+
+    unwind_loops(aug)
+}
+
+/// Walks an augmented vertex sequence (original vertices plus self-crossing
+/// points, in traversal order) and peels off a simple loop every time a
+/// vertex repeats -- the standard way to decompose a self-crossing closed
+/// walk into simple cycles.
+fn unwind_loops<T: IntPoint>(aug: Vec<T>) -> Vec<Vec<T>> {
+    let mut stack: Vec<T> = Vec::new();
+    let mut seen: HashMap<(CInt, CInt), usize> = HashMap::new();
+    let mut loops = Vec::new();
+
+    for pt in aug {
+        let key = (pt.get_x(), pt.get_y());
+        if let Some(idx) = seen.remove(&key) {
+            let closed_loop = stack.split_off(idx);
+            for p in &closed_loop {
+                seen.remove(&(p.get_x(), p.get_y()));
+            }
+            if closed_loop.len() >= 3 {
+                loops.push(closed_loop);
+            }
+            seen.insert(key, stack.len());
+            stack.push(pt);
+        } else {
+            seen.insert(key, stack.len());
+            stack.push(pt);
+        }
+    }
+
+    if stack.len() >= 3 {
+        loops.push(stack);
+    }
+
+    loops
+}
+
+/// Intersection point of open segments `p1`-`p2` and `p3`-`p4`, if any
+/// (endpoints themselves don't count as crossings).
+fn segment_intersection<T: IntPoint>(p1: T, p2: T, p3: T, p4: T) -> Option<T> {
+    let (x1, y1) = (p1.get_x() as f64, p1.get_y() as f64);
+    let (x2, y2) = (p2.get_x() as f64, p2.get_y() as f64);
+    let (x3, y3) = (p3.get_x() as f64, p3.get_y() as f64);
+    let (x4, y4) = (p4.get_x() as f64, p4.get_y() as f64);
+
+    let denom = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+    if denom.abs() < 1.0e-9 {
+        return None;
+    }
+
+    let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) / denom;
+    let u = ((x1 - x3) * (y1 - y2) - (y1 - y3) * (x1 - x2)) / denom;
+    const EPS: f64 = 1.0e-9;
+    if t > EPS && t < 1.0 - EPS && u > EPS && u < 1.0 - EPS {
+        let x = x1 + t * (x2 - x1);
+        let y = y1 + t * (y2 - y1);
+        Some(T::new(x.round() as CInt, y.round() as CInt))
+    } else {
+        None
+    }
+}
+
+/// Parametric position of `pt` (assumed colinear with `a`-`b`) along the
+/// segment, used only to order multiple crossings found on the same edge.
+fn param_along<T: IntPoint>(a: T, b: T, pt: T) -> f64 {
+    let dx = (b.get_x() - a.get_x()) as f64;
+    let dy = (b.get_y() - a.get_y()) as f64;
+    if dx.abs() > dy.abs() {
+        (pt.get_x() - a.get_x()) as f64 / dx
+    } else {
+        (pt.get_y() - a.get_y()) as f64 / dy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+
+    #[test]
+    fn bowtie_splits_into_two_simple_triangles() {
+        let bowtie = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(10, 10),
+                IntPoint3d::new(10, 0),
+                IntPoint3d::new(0, 10),
+            ],
+        };
+        let result = simplify_polygon(&bowtie, PolyFillType::NonZero);
+
+        assert_eq!(result.paths.len(), 2);
+        for path in &result.paths {
+            assert_eq!(path.poly.len(), 3);
+        }
+    }
+
+    #[test]
+    fn simple_polygon_is_unaffected() {
+        let square = Path {
+            poly: vec![
+                IntPoint3d::new(0, 0),
+                IntPoint3d::new(10, 0),
+                IntPoint3d::new(10, 10),
+                IntPoint3d::new(0, 10),
+            ],
+        };
+        let result = simplify_polygon(&square, PolyFillType::NonZero);
+
+        assert_eq!(result.paths.len(), 1);
+        assert_eq!(result.paths[0].poly.len(), 4);
+    }
+}
@@ -0,0 +1,111 @@
+//! A small thread pool: worker threads block on a single shared job queue
+//! and pick up the next closure as soon as they're free.
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+enum Message {
+    NewJob(Job),
+    Terminate,
+}
+
+/// A fixed-size pool of worker threads consuming a shared job queue.
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: mpsc::Sender<Message>,
+}
+
+struct Worker {
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Spawns `size` worker threads (clamped to at least 1).
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        let (sender, receiver) = mpsc::channel::<Message>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size).map(|_| Worker::new(receiver.clone())).collect();
+
+        ThreadPool { workers: workers, sender: sender }
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues a single job, to be picked up by the next free worker.
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.sender
+            .send(Message::NewJob(Box::new(job)))
+            .expect("thread pool worker disconnected");
+    }
+
+    /// Runs `f` over every item of `jobs` across the pool and blocks until
+    /// every result is back, preserving input order.
+    pub fn map<T, R, F>(&self, jobs: Vec<T>, f: F) -> Vec<R>
+    where
+        T: Send + 'static,
+        R: Send + 'static,
+        F: Fn(T) -> R + Send + Sync + 'static,
+    {
+        let n = jobs.len();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let f = Arc::new(f);
+        let (tx, rx) = mpsc::channel();
+
+        for (i, job) in jobs.into_iter().enumerate() {
+            let tx = tx.clone();
+            let f = f.clone();
+            self.execute(move || {
+                let result = f(job);
+                tx.send((i, result)).expect("result channel closed");
+            });
+        }
+        drop(tx);
+
+        let mut results: Vec<Option<R>> = (0..n).map(|_| None).collect();
+        for _ in 0..n {
+            let (i, r) = rx.recv().expect("a worker dropped its job without producing a result");
+            results[i] = Some(r);
+        }
+        results.into_iter().map(|r| r.unwrap()).collect()
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        for _ in &self.workers {
+            // workers that have already exited (panicked) simply drop this
+            self.sender.send(Message::Terminate).ok();
+        }
+        for worker in &mut self.workers {
+            if let Some(handle) = worker.handle.take() {
+                handle.join().ok();
+            }
+        }
+    }
+}
+
+impl Worker {
+    fn new(receiver: Arc<Mutex<mpsc::Receiver<Message>>>) -> Worker {
+        let handle = thread::spawn(move || loop {
+            let message = receiver.lock().unwrap().recv();
+            match message {
+                Ok(Message::NewJob(job)) => job(),
+                Ok(Message::Terminate) | Err(_) => break,
+            }
+        });
+        Worker { handle: Some(handle) }
+    }
+}
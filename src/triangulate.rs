@@ -0,0 +1,242 @@
+//! Triangulation of clipped output. Downstream consumers (GPU tessellators,
+//! renderers) can't feed `PolyTree`/`Paths` straight to a rasterizer; this
+//! module bridges each outer contour's holes into it (via a mutually
+//! visible vertex pair) and ear-clips the resulting simple polygon.
+
+use edge::slopes_equal_point3;
+use point::IntPoint;
+use {Path, PolyTree};
+
+/// Turns every outer contour (with its holes bridged in) of `tree` into a
+/// flat, CCW-wound triangle list.
+pub fn triangulate<T: IntPoint>(tree: &PolyTree<T>) -> Vec<[T; 3]> {
+    let mut triangles = Vec::new();
+
+    // Same parity rule as `PolyNode::is_hole`, but walked through `tree`
+    // directly: `is_hole` locks the node's own tree, which the caller
+    // already holds while it lends us `tree`.
+    let is_hole = |node: &::node::PolyNode<T>| {
+        let mut result = false;
+        let mut parent = node.parent;
+        while let Some(idx) = parent {
+            result = !result;
+            parent = tree.all_nodes[idx.node_idx].parent;
+        }
+        result
+    };
+
+    for node in &tree.all_nodes {
+        if is_hole(node) || node.contour.poly.len() < 3 {
+            continue;
+        }
+
+        let mut ring = node.contour.poly.clone();
+        for hole_idx in &node.childs {
+            let hole_node = &tree.all_nodes[hole_idx.node_idx];
+            if !is_hole(hole_node) || hole_node.contour.poly.len() < 3 {
+                continue;
+            }
+            bridge_hole(&mut ring, &hole_node.contour.poly);
+        }
+
+        ear_clip(&ring, &mut triangles);
+    }
+
+    triangles
+}
+
+/// Splices `hole` into `outer` via a zero-width bridge between the hole
+/// vertex with the greatest x and the nearest outer edge it can see by
+/// casting a ray in the `+x` direction.
+fn bridge_hole<T: IntPoint>(outer: &mut Vec<T>, hole: &[T]) {
+    if hole.is_empty() || outer.is_empty() {
+        return;
+    }
+
+    let (h_idx, h_pt) = hole
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, p)| p.get_x())
+        .map(|(i, p)| (i, *p))
+        .unwrap();
+
+    let mut best_dist = f64::MAX;
+    let mut bridge_idx = 0usize;
+    let n = outer.len();
+
+    for i in 0..n {
+        let a = outer[i];
+        let b = outer[(i + 1) % n];
+        let (ay, by) = (a.get_y(), b.get_y());
+        // only edges that straddle the ray's y coordinate
+        if (ay > h_pt.get_y()) == (by > h_pt.get_y()) {
+            continue;
+        }
+        let t = (h_pt.get_y() - ay) as f64 / (by - ay) as f64;
+        let ix = a.get_x() as f64 + t * (b.get_x() - a.get_x()) as f64;
+        if ix < h_pt.get_x() as f64 {
+            continue;
+        }
+        let dist = ix - h_pt.get_x() as f64;
+        if dist < best_dist {
+            best_dist = dist;
+            bridge_idx = if a.get_x() >= b.get_x() { i } else { (i + 1) % n };
+        }
+    }
+
+    let hole_len = hole.len();
+    let mut spliced = Vec::with_capacity(n + hole_len + 2);
+    for (k, p) in outer.iter().enumerate() {
+        spliced.push(*p);
+        if k == bridge_idx {
+            // walk the hole backwards (opposite winding) from h_idx back to h_idx
+            for j in 0..=hole_len {
+                let idx = (h_idx + hole_len - (j % hole_len)) % hole_len;
+                spliced.push(hole[idx]);
+            }
+            spliced.push(*p);
+        }
+    }
+    *outer = spliced;
+}
+
+/// Ear-clips a simple polygon `ring` into CCW-wound triangles. Bails
+/// gracefully (emits whatever was already found) on degenerate input.
+fn ear_clip<T: IntPoint>(ring: &[T], out: &mut Vec<[T; 3]>) {
+    let mut idx: Vec<usize> = (0..ring.len()).collect();
+    if idx.len() < 3 {
+        return;
+    }
+
+    let ccw = (Path { poly: ring.to_vec() }).orientation();
+    let max_iters = ring.len() * ring.len() + 16;
+    let mut iters = 0;
+
+    while idx.len() > 3 && iters < max_iters {
+        iters += 1;
+        let n = idx.len();
+        let mut found = false;
+
+        for k in 0..n {
+            let ip = idx[(k + n - 1) % n];
+            let ic = idx[k];
+            let inext = idx[(k + 1) % n];
+            let a = ring[ip];
+            let b = ring[ic];
+            let c = ring[inext];
+
+            if slopes_equal_point3(&a, &b, &c) {
+                continue; // collinear/zero-area ear
+            }
+
+            let cross = cross_z(a, b, c);
+            if (cross >= 0.0) != ccw {
+                continue; // reflex vertex, not an ear
+            }
+
+            if idx.iter().any(|&m| {
+                m != ip && m != ic && m != inext && point_in_triangle(ring[m], a, b, c)
+            }) {
+                continue;
+            }
+
+            out.push(if ccw { [a, b, c] } else { [a, c, b] });
+            idx.remove(k);
+            found = true;
+            break;
+        }
+
+        if !found {
+            break;
+        }
+    }
+
+    if idx.len() == 3 {
+        let a = ring[idx[0]];
+        let b = ring[idx[1]];
+        let c = ring[idx[2]];
+        out.push(if ccw { [a, b, c] } else { [a, c, b] });
+    }
+}
+
+fn cross_z<T: IntPoint>(a: T, b: T, c: T) -> f64 {
+    let abx = (b.get_x() - a.get_x()) as f64;
+    let aby = (b.get_y() - a.get_y()) as f64;
+    let acx = (c.get_x() - a.get_x()) as f64;
+    let acy = (c.get_y() - a.get_y()) as f64;
+    abx * acy - aby * acx
+}
+
+fn point_in_triangle<T: IntPoint>(p: T, a: T, b: T, c: T) -> bool {
+    let d1 = cross_z(a, b, p);
+    let d2 = cross_z(b, c, p);
+    let d3 = cross_z(c, a, p);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::IntPoint3d;
+    use node::PolyNode;
+    use {EndType, JoinType, PolyNodeIndex};
+    use std::sync::{Arc, Mutex};
+
+    fn standalone_node(tree: Arc<Mutex<PolyTree<IntPoint3d>>>, glob_index: usize, parent: Option<usize>) -> PolyNode<IntPoint3d> {
+        PolyNode {
+            tree,
+            glob_index: PolyNodeIndex { node_idx: glob_index },
+            index: 0,
+            contour: Path { poly: Vec::new() },
+            parent: parent.map(|idx| PolyNodeIndex { node_idx: idx }),
+            childs: Vec::new(),
+            is_open: false,
+            join_type: JoinType::Miter,
+            end_type: EndType::ClosedPolygon,
+        }
+    }
+
+    #[test]
+    fn is_hole_parity_matches_even_depth_is_outer() {
+        // Three-generation chain: root -> child -> grandchild.
+        let tree = Arc::new(Mutex::new(PolyTree::new()));
+        {
+            let mut locked = tree.lock().unwrap();
+            locked.all_nodes.push(standalone_node(tree.clone(), 0, None));
+            locked.all_nodes.push(standalone_node(tree.clone(), 1, Some(0)));
+            locked.all_nodes.push(standalone_node(tree.clone(), 2, Some(1)));
+        }
+
+        // Built standalone (not through a live lock on `tree`) so is_hole's
+        // own internal locking doesn't deadlock against an already-held guard.
+        let root = standalone_node(tree.clone(), 0, None);
+        let child = standalone_node(tree.clone(), 1, Some(0));
+        let grandchild = standalone_node(tree.clone(), 2, Some(1));
+
+        assert!(!root.is_hole(), "a root-level contour is an outer boundary, not a hole");
+        assert!(child.is_hole(), "a direct child of an outer contour is a hole");
+        assert!(!grandchild.is_hole(), "a hole's own child is an outer boundary again");
+    }
+
+    #[test]
+    fn ear_clips_a_simple_square_into_two_triangles() {
+        let square = vec![
+            IntPoint3d::new(0, 0),
+            IntPoint3d::new(20, 0),
+            IntPoint3d::new(20, 20),
+            IntPoint3d::new(0, 20),
+        ];
+
+        let mut triangles = Vec::new();
+        ear_clip(&square, &mut triangles);
+
+        assert_eq!(triangles.len(), 2);
+        let total_area: f64 = triangles
+            .iter()
+            .map(|t| (Path { poly: t.to_vec() }).area().abs())
+            .sum();
+        assert!((total_area - 400.0).abs() < 1.0e-6, "got {}", total_area);
+    }
+}